@@ -177,8 +177,13 @@ where
     /// Creates a component map from the data and inner components
     /// of this block entity.
     pub fn create_components(&self) -> ComponentMap<'a, Cx> {
+        self.components.merged(&self.data_components())
+    }
+
+    /// Creates a component map from the data of this block entity only,
+    /// without the inner components.
+    pub fn data_components(&self) -> ComponentMap<'a, Cx> {
         let mut builder = ComponentMap::builder();
-        builder.extend(self.components.iter());
         self.data.insert_components(&mut builder);
         builder.build()
     }
@@ -346,6 +351,37 @@ where
     }
 }
 
+impl<Cx> BlockEntity<'static, Cx>
+where
+    Cx: ProvideBlockStateExtTy,
+{
+    /// Downcasts this type erased block entity into block entity with a concrete `'static`
+    /// data type.
+    ///
+    /// This function is similar to `downcast_ref`, but it is safe as the data of a `'static`
+    /// block entity is `'static`, so type `T` could not be a lifetime-erased variant of a
+    /// shorter-lived data type.
+    #[inline]
+    pub fn downcast_ref_static<T: 'static>(&self) -> Option<&RawBlockEntity<'static, T, Cx>> {
+        // SAFETY: the data of a `'static` block entity is `'static`.
+        unsafe { self.downcast_ref() }
+    }
+
+    /// Downcasts this type erased block entity into block entity with a concrete `'static`
+    /// data type, with mutable access.
+    ///
+    /// This function is similar to `downcast_mut`, but it is safe as the data of a `'static`
+    /// block entity is `'static`, so type `T` could not be a lifetime-erased variant of a
+    /// shorter-lived data type.
+    #[inline]
+    pub fn downcast_mut_static<T: 'static>(
+        &mut self,
+    ) -> Option<&mut RawBlockEntity<'static, T, Cx>> {
+        // SAFETY: the data of a `'static` block entity is `'static`.
+        unsafe { self.downcast_mut() }
+    }
+}
+
 /// A trait for providing block entities.
 ///
 /// This should be implemented for [`ProvideBlockStateExtTy::BlockStateExt`]s.
@@ -364,3 +400,6 @@ where
         &'s self,
     ) -> Option<impl FnOnce(BlockPos) -> Box<BlockEntity<'w, Cx>> + 's>;
 }
+
+#[cfg(test)]
+mod tests;
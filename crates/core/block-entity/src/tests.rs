@@ -0,0 +1,125 @@
+use std::sync::LazyLock;
+
+use ::component::{
+    map::{Builder, ComponentMap},
+    ComponentType, PacketCodec, RawErasedComponentType,
+};
+use rimecraft_block::{BlockState, RawBlock, Settings};
+use rimecraft_registry::{Reg, Registry, RegistryKey, RegistryMut};
+use rimecraft_voxel_math::BlockPos;
+use serde::{Deserialize, Serialize};
+use test_global::{Id, TestContext as Context};
+
+use crate::{BlockEntity, Data, DynRawBlockEntityType, RawBlockEntity, RawBlockEntityType};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Counter {
+    value: u32,
+}
+
+impl Data<'static, Context> for Counter {
+    fn insert_components(&self, builder: &mut Builder<'static, Context>) {
+        let ty = COMPONENT_TYPES.of_raw(0).expect("missing component type");
+        builder.insert(ty, self.value);
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Other;
+
+impl Data<'static, Context> for Other {}
+
+#[derive(Debug)]
+struct CounterType;
+
+impl RawBlockEntityType<Context> for CounterType {
+    fn supports(&self, _state: &BlockState<'_, Context>) -> bool {
+        true
+    }
+
+    fn instantiate<'w>(
+        &self,
+        _pos: BlockPos,
+        _state: BlockState<'w, Context>,
+    ) -> Option<Box<BlockEntity<'w, Context>>> {
+        None
+    }
+}
+
+const PACKET_CODEC: PacketCodec<'static, u32> = ::component::packet_codec_edcode();
+const COMPONENT_TYPE: ComponentType<'static, u32> = ComponentType::transient(&PACKET_CODEC);
+
+static COMPONENT_TYPES: LazyLock<Registry<Id, RawErasedComponentType<'static, Context>>> =
+    LazyLock::new(|| registry("component_type", (&COMPONENT_TYPE).into()));
+
+const fn id(path: &'static str) -> Id {
+    unsafe { Id::const_new("test", path) }
+}
+
+fn registry<T>(path: &'static str, value: T) -> Registry<Id, T> {
+    let mut registry = RegistryMut::new(RegistryKey::with_root(id(path)));
+    assert!(registry
+        .register(RegistryKey::new(id(path), id("entry")), value)
+        .is_ok());
+    registry.into()
+}
+
+fn counter(value: u32) -> Box<BlockEntity<'static, Context>> {
+    let blocks = registry::<RawBlock<'static, Context>>("block", Settings::default().into()).leak();
+    let block = blocks.of_raw(0).expect("missing block");
+    let types = registry::<DynRawBlockEntityType<'static, Context>>(
+        "block_entity_type",
+        Box::new(CounterType),
+    )
+    .leak();
+    let state = BlockState {
+        block,
+        state: Reg::into_inner(block).states().default_state(),
+    };
+    Box::new(RawBlockEntity::new(
+        types.of_raw(0).expect("missing block entity type"),
+        BlockPos::new(0, 0, 0),
+        state,
+        Counter { value },
+    ))
+}
+
+#[test]
+fn downcast_static() {
+    let mut be = counter(1);
+    assert_eq!(
+        be.downcast_ref_static::<Counter>()
+            .expect("downcast failed")
+            .data()
+            .value,
+        1
+    );
+    be.downcast_mut_static::<Counter>()
+        .expect("downcast failed")
+        .data_mut()
+        .value = 2;
+    assert_eq!(
+        be.downcast_ref_static::<Counter>()
+            .expect("downcast failed")
+            .data()
+            .value,
+        2
+    );
+
+    assert!(be.downcast_ref_static::<Other>().is_none());
+    assert!(be.downcast_mut_static::<Other>().is_none());
+}
+
+#[test]
+fn data_components() {
+    let mut be = counter(3);
+    let data = be.data_components();
+    assert_eq!(data.len(), 1);
+    assert_eq!(data.get_static(&COMPONENT_TYPE), Some(&3));
+
+    let mut builder = ComponentMap::builder();
+    builder.insert(COMPONENT_TYPES.of_raw(0).expect("missing type"), 4u32);
+    *be.components_mut() = builder.build();
+    assert_eq!(be.data_components().get_static(&COMPONENT_TYPE), Some(&3));
+    assert_eq!(be.create_components().get_static(&COMPONENT_TYPE), Some(&3));
+}
@@ -1,6 +1,6 @@
 //! `ComponentChanges` implementation.
 
-use std::{cell::UnsafeCell, fmt::Debug, marker::PhantomData, str::FromStr, sync::OnceLock};
+use std::{fmt::Debug, marker::PhantomData, str::FromStr, sync::OnceLock};
 
 use ahash::{AHashMap, AHashSet};
 use bytes::{Buf, BufMut};
@@ -12,8 +12,7 @@ use serde::{de::DeserializeSeed, ser::SerializeMap, Deserialize, Serialize};
 
 use crate::{
     map::{CompTyCell, ComponentMap},
-    ComponentType, ErasedComponentType, Object, RawErasedComponentType, UnsafeDebugIter,
-    UnsafeSerdeCodec,
+    ComponentType, ErasedComponentType, Object, RawErasedComponentType, UnsafeSerdeCodec,
 };
 
 /// Changes of components.
@@ -101,6 +100,18 @@ where
         }
     }
 
+    /// Returns the length of the changes when encoded, without allocating the encoded bytes.
+    ///
+    /// If encoding fails, the length written before the failure is returned.
+    pub fn encoded_len_hint(&self) -> usize {
+        let mut counter = LenCounter {
+            len: 0,
+            scratch: [0; 64],
+        };
+        self.encode(&mut counter).ok();
+        counter.len
+    }
+
     /// Converts the changes into a pair of added components and removed component types.
     pub fn into_added_removed_pair(
         self,
@@ -300,6 +311,34 @@ where
     }
 }
 
+/// A buffer counting bytes written into it, discarding the bytes.
+struct LenCounter {
+    len: usize,
+    scratch: [u8; 64],
+}
+
+unsafe impl BufMut for LenCounter {
+    #[inline]
+    fn remaining_mut(&self) -> usize {
+        usize::MAX - self.len
+    }
+
+    #[inline]
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        self.len += cnt;
+    }
+
+    #[inline]
+    fn chunk_mut(&mut self) -> &mut bytes::buf::UninitSlice {
+        bytes::buf::UninitSlice::new(&mut self.scratch)
+    }
+
+    #[inline]
+    fn put_slice(&mut self, src: &[u8]) {
+        self.len += src.len();
+    }
+}
+
 /// Builder for [`ComponentChanges`].
 pub struct Builder<'a, Cx>
 where
@@ -463,11 +502,11 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         Debug::fmt(
-            &UnsafeDebugIter(UnsafeCell::new(
-                self.changed
-                    .iter()
-                    .map(|(k, v)| (k.0, v.as_ref().map(|v| (k.0.f.util.dbg)(&**v)))),
-            )),
+            &self
+                .changed
+                .iter()
+                .map(|(k, v)| (k.0, v.as_ref().map(|v| (k.0.f.util.dbg)(&**v))))
+                .collect::<Vec<_>>(),
             f,
         )
     }
@@ -479,6 +518,6 @@ where
     Cx::Id: Debug,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        Debug::fmt(&UnsafeDebugIter(UnsafeCell::new(self.changes.keys())), f)
+        Debug::fmt(&self.changes.keys().collect::<Vec<_>>(), f)
     }
 }
@@ -1,6 +1,6 @@
 //! Minecraft Component implementation.
 
-use std::{any::TypeId, cell::UnsafeCell, fmt::Debug, hash::Hash, marker::PhantomData};
+use std::{any::TypeId, fmt::Debug, hash::Hash, marker::PhantomData};
 
 use bytes::{Buf, BufMut};
 use edcode2::{Decode, Encode};
@@ -8,7 +8,7 @@ use rimecraft_global_cx::{
     nbt::{ReadNbt, UpdateNbt, WriteNbt},
     ProvideIdTy,
 };
-use rimecraft_registry::{ProvideRegistry, Reg};
+use rimecraft_registry::{ProvideRegistry, Reg, Registry};
 use serde::{de::DeserializeOwned, Serialize};
 
 type Object<'a> = dyn Any + Send + Sync + 'a;
@@ -24,6 +24,8 @@ use dyn_any::Any;
 
 pub use ahash::{AHashMap, AHashSet};
 
+use map::ComponentMap;
+
 /// Type of a component data.
 ///
 /// The type `T` should be unique for each component, as it's used to identify the component.
@@ -45,6 +47,16 @@ impl<T> ComponentType<'_, T> {
     }
 }
 
+impl<'a, T> ComponentType<'a, T> {
+    /// Returns the default value of the component, if declared.
+    #[inline]
+    pub fn default_value(&self) -> Option<&'a T> {
+        self.f
+            .default_value
+            .map(|value| unsafe { &*(std::ptr::from_ref::<Object<'_>>(value.0) as *const T) })
+    }
+}
+
 impl<'a, T> ComponentType<'a, T> {
     /// Creates a builder of component type.
     #[inline]
@@ -52,6 +64,7 @@ impl<'a, T> ComponentType<'a, T> {
         TypeBuilder {
             serde_codec: None,
             packet_codec: None,
+            default_value: None,
             _marker: PhantomData,
         }
     }
@@ -61,6 +74,26 @@ impl<'a, T> ComponentType<'a, T>
 where
     T: Clone + Eq + Hash + Debug + Send + Sync + 'a,
 {
+    /// Creates a transient component type with the given packet codec.
+    ///
+    /// Transient components are not serialized.
+    #[inline]
+    pub const fn transient(packet_codec: &'a PacketCodec<'a, T>) -> Self {
+        Self::builder::<()>().packet_codec(packet_codec).build()
+    }
+
+    /// Creates a persistent component type with the given packet codec and serde codec.
+    #[inline]
+    pub const fn persistent(
+        packet_codec: &'a PacketCodec<'a, T>,
+        serde_codec: &'a SerdeCodec<'a, T>,
+    ) -> Self {
+        Self::builder::<()>()
+            .packet_codec(packet_codec)
+            .serde_codec(serde_codec)
+            .build()
+    }
+
     const UTIL: DynUtil<'a> = DynUtil {
         clone: |obj| {
             Box::new(unsafe { &*(std::ptr::from_ref::<Object<'_>>(obj) as *const T) }.clone())
@@ -169,6 +202,7 @@ where
 pub struct TypeBuilder<'a, T, Cx> {
     serde_codec: Option<&'a UnsafeSerdeCodec<'a>>,
     packet_codec: Option<&'a UnsafePacketCodec<'a>>,
+    default_value: Option<DefaultValue<'a>>,
     _marker: PhantomData<(T, Cx)>,
 }
 
@@ -194,8 +228,19 @@ impl<'a, T, Cx> TypeBuilder<'a, T, Cx>
 where
     T: Clone + Eq + Hash + Debug + Send + Sync + 'a,
 {
+    /// Applies the given default value, which is used by [`default_map`].
+    pub const fn default_value(self, value: &'a T) -> Self {
+        Self {
+            default_value: Some(DefaultValue(value)),
+            ..self
+        }
+    }
+
     /// Builds a new [`ComponentType`] with the given codecs.
     ///
+    /// The codecs are guaranteed to target `T`, as they could only be applied
+    /// through the typed setters of this builder.
+    ///
     /// # Panics
     ///
     /// Panics if the packet codec is not set.
@@ -208,6 +253,7 @@ where
                     None => panic!("packet codec is required"),
                 },
                 util: &ComponentType::<T>::UTIL,
+                default_value: self.default_value,
             },
             _marker: PhantomData,
         }
@@ -292,6 +338,16 @@ struct Funcs<'a> {
     serde_codec: Option<&'a UnsafeSerdeCodec<'a>>,
     packet_codec: &'a UnsafePacketCodec<'a>,
     util: &'a DynUtil<'a>,
+    default_value: Option<DefaultValue<'a>>,
+}
+
+#[derive(Clone, Copy)]
+struct DefaultValue<'a>(&'a Object<'a>);
+
+impl Debug for DefaultValue<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("DefaultValue")
+    }
 }
 
 impl<'a, Cx> RawErasedComponentType<'a, Cx> {
@@ -373,7 +429,7 @@ where
     Cx: ProvideRegistry<'r, K, Self>,
 {
     #[inline]
-    fn registry() -> &'r rimecraft_registry::Registry<K, Self> {
+    fn registry() -> &'r Registry<K, Self> {
         Cx::registry()
     }
 }
@@ -390,18 +446,23 @@ impl<Cx> Clone for RawErasedComponentType<'_, Cx> {
 pub type ErasedComponentType<'a, Cx> =
     Reg<'a, <Cx as ProvideIdTy>::Id, RawErasedComponentType<'a, Cx>>;
 
-struct UnsafeDebugIter<I>(UnsafeCell<I>);
-
-impl<I> Debug for UnsafeDebugIter<I>
+/// Creates a component map holding the default values of all component types
+/// in the given registry.
+///
+/// Component types without a declared default value are skipped.
+pub fn default_map<'a, Cx>(
+    registry: &'a Registry<Cx::Id, RawErasedComponentType<'a, Cx>>,
+) -> ComponentMap<'a, Cx>
 where
-    I: Iterator<Item: Debug>,
+    Cx: ProvideIdTy,
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        unsafe {
-            let it = &mut *self.0.get();
-            f.debug_list().entries(it).finish()
-        }
-    }
+    let mut builder = ComponentMap::builder();
+    builder.extend(
+        registry
+            .entries()
+            .filter_map(|ty| ty.f.default_value.map(|value| (ty, value.0))),
+    );
+    builder.build()
 }
 
 #[cfg(all(test, feature = "test"))]
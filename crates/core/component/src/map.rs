@@ -1,19 +1,18 @@
 //! Component map implementation.
 
 use std::{
-    borrow::Borrow, cell::UnsafeCell, collections::hash_map, fmt::Debug, hash::Hash,
-    marker::PhantomData, sync::Arc,
+    borrow::Borrow, collections::hash_map, fmt::Debug, hash::Hash, marker::PhantomData, sync::Arc,
 };
 
 use ahash::AHashMap;
 use rimecraft_global_cx::ProvideIdTy;
 use rimecraft_maybe::{Maybe, SimpleOwned};
-use rimecraft_registry::ProvideRegistry;
+use rimecraft_registry::{ProvideRegistry, Reg};
 use serde::{Deserialize, Serialize};
 
 use crate::{
     changes::ComponentChanges, dyn_any, ComponentType, ErasedComponentType, Object,
-    RawErasedComponentType, UnsafeDebugIter, UnsafeSerdeCodec,
+    RawErasedComponentType, UnsafeSerdeCodec,
 };
 
 #[repr(transparent)]
@@ -410,6 +409,19 @@ where
         self.into_iter()
     }
 
+    /// Returns an iterator over the components in this map, ordered by
+    /// the identifiers of their types.
+    ///
+    /// Unlike [`Self::iter`], the order is deterministic across runs, and does
+    /// not depend on the registration order of the types.
+    pub fn iter_sorted<'s>(
+        &'s self,
+    ) -> std::vec::IntoIter<(ErasedComponentType<'a, Cx>, &'s Object<'a>)> {
+        let mut entries: Vec<_> = self.iter().collect();
+        entries.sort_by_cached_key(|(ty, _)| Reg::id(*ty).to_string());
+        entries.into_iter()
+    }
+
     /// Returns the changes of this map.
     pub fn changes(&self) -> Option<ComponentChanges<'a, '_, Cx>> {
         if let MapInner::Patched { changes, .. } = &self.0 {
@@ -421,6 +433,69 @@ where
             None
         }
     }
+
+    /// Splits this map into two simple maps, containing the persistent and the
+    /// transient components respectively.
+    pub fn partition_persistence(&self) -> (Self, Self) {
+        let (persistent, transient): (Vec<_>, Vec<_>) =
+            self.iter().partition(|(ty, _)| !ty.is_transient());
+        let build = |entries: Vec<_>| {
+            let mut builder = Self::builder_with_capacity(entries.len());
+            builder.extend(entries);
+            builder.build()
+        };
+        (build(persistent), build(transient))
+    }
+
+    /// Merges this map with the given `overrides` into a new simple map.
+    ///
+    /// Components present in both maps take the value from `overrides`,
+    /// regardless of whether they are transient or persistent.
+    pub fn merged(&self, overrides: &Self) -> Self {
+        let mut builder = Self::builder_with_capacity(self.len() + overrides.len());
+        builder.extend(self.iter());
+        builder.extend(overrides.iter());
+        builder.build()
+    }
+
+    /// Computes the changes that turn the given `base` map into this map.
+    ///
+    /// Components present in this map but absent from, or unequal to those in
+    /// `base` are recorded as additions, and components only present in `base`
+    /// are recorded as removals.
+    pub fn diff_against<'cow>(&self, base: &Self) -> ComponentChanges<'a, 'cow, Cx> {
+        let mut changed: AHashMap<_, _> = self
+            .iter()
+            .filter(|(ty, obj)| base.get_raw(ty).is_none_or(|o| !(ty.f.util.eq)(*obj, o)))
+            .map(|(ty, obj)| (CompTyCell(ty), Some((ty.f.util.clone)(obj))))
+            .collect();
+        changed.extend(
+            base.iter()
+                .filter(|(ty, _)| !self.contains_raw(ty))
+                .map(|(ty, _)| (CompTyCell(ty), None)),
+        );
+
+        ComponentChanges {
+            ser_count: changed.keys().filter(|cell| !cell.0.is_transient()).count(),
+            changed: Maybe::Owned(SimpleOwned(changed)),
+        }
+    }
+}
+
+impl<Cx> ComponentMap<'static, Cx>
+where
+    Cx: ProvideIdTy,
+{
+    /// Gets the component with given `'static` type.
+    ///
+    /// This function is similar to `get`, but it is safe as every component of a
+    /// `'static` map is `'static`, so type `T` could not be a lifetime-erased
+    /// variant of a shorter-lived component type.
+    #[inline]
+    pub fn get_static<T: 'static>(&self, ty: &ComponentType<'static, T>) -> Option<&T> {
+        // SAFETY: components of a `'static` map are `'static`.
+        unsafe { self.get(ty) }
+    }
 }
 
 impl<'a, 's, Cx> IntoIterator for &'s ComponentMap<'a, Cx>
@@ -719,19 +794,20 @@ where
                 .field("base", base)
                 .field(
                     "changes",
-                    &UnsafeDebugIter(UnsafeCell::new(
-                        changes
-                            .iter()
-                            .map(|(k, v)| (k, v.as_ref().map(|obj| (k.0.f.util.dbg)(obj)))),
-                    )),
+                    &changes
+                        .iter()
+                        .map(|(k, v)| (k, v.as_ref().map(|obj| (k.0.f.util.dbg)(obj))))
+                        .collect::<Vec<_>>(),
                 )
                 .field("changes_count", changes_count)
                 .finish(),
             MapInner::Simple(map) => f
                 .debug_tuple("SimpleComponentMap")
-                .field(&UnsafeDebugIter(UnsafeCell::new(
-                    map.iter().map(|(k, v)| (k, (k.0.f.util.dbg)(v))),
-                )))
+                .field(
+                    &map.iter()
+                        .map(|(k, v)| (k, (k.0.f.util.dbg)(v)))
+                        .collect::<Vec<_>>(),
+                )
                 .finish(),
         }
     }
@@ -751,7 +827,7 @@ where
                 changes_it: _,
             } => f
                 .debug_struct("PatchedComponentMapIter")
-                .field("changes", &UnsafeDebugIter(UnsafeCell::new(changes.keys())))
+                .field("changes", &changes.keys().collect::<Vec<_>>())
                 .field("base_it", base_it)
                 .finish(),
             IterInner::Simple(_it) => f.debug_tuple("SimpleComponentMapIter").finish(),
@@ -766,7 +842,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ComponentMapBuilder")
-            .field("map", &UnsafeDebugIter(UnsafeCell::new(self.map.keys())))
+            .field("map", &self.map.keys().collect::<Vec<_>>())
             .finish()
     }
 }
@@ -782,7 +858,7 @@ where
     {
         use serde::ser::SerializeMap;
         let mut map = serializer.serialize_map(None)?;
-        for (ty, val) in self.iter() {
+        for (ty, val) in self.iter_sorted() {
             if let Some(codec) = ty.f.serde_codec {
                 map.serialize_entry(&ty, (codec.ser)(val))?;
             }
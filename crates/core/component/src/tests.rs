@@ -65,6 +65,9 @@ fn type_transient_check() {
         .serde_codec(&SERDE_CODEC)
         .build();
     assert!(!ty.is_transient());
+
+    assert!(ComponentType::transient(&PACKET_CODEC_EDCODE).is_transient());
+    assert!(!ComponentType::persistent(&PACKET_CODEC_NBT, &SERDE_CODEC).is_transient());
 }
 
 const TYPE_TRANSIENT_EDCODE: ComponentType<'static, Foo> =
@@ -165,6 +168,34 @@ fn built_map() {
     );
 }
 
+#[test]
+fn built_map_get_static() {
+    init_registry();
+    let reg = crate::test_global_integration::registry();
+    let edcode_ty = reg
+        .get(&TYPE_TRANSIENT_EDCODE_KEY)
+        .expect("invalid registry");
+
+    let mut builder = ComponentMap::builder();
+    builder.insert(
+        edcode_ty,
+        Foo {
+            value: 114,
+            info: "hello".to_owned(),
+        },
+    );
+    let map = builder.build();
+
+    assert_eq!(
+        map.get_static(&TYPE_TRANSIENT_EDCODE)
+            .expect("missing edcode_ty")
+            .value,
+        114,
+        "edcode_ty value mismatch"
+    );
+    assert!(map.get_static(&TYPE_PERSISTENT).is_none());
+}
+
 #[test]
 fn iter_map() {
     init_registry();
@@ -328,6 +359,42 @@ fn map_serde() {
     );
 }
 
+#[test]
+fn iter_sorted() {
+    init_registry();
+    let reg = crate::test_global_integration::registry();
+    let edcode_ty = reg
+        .get(&TYPE_TRANSIENT_EDCODE_KEY)
+        .expect("invalid registry");
+    let persistent_ty = reg.get(&TYPE_PERSISTENT_KEY).expect("invalid registry");
+
+    let mut builder = ComponentMap::builder();
+    builder.insert(
+        persistent_ty,
+        Foo {
+            value: 114,
+            info: "hello".to_owned(),
+        },
+    );
+    builder.insert(
+        edcode_ty,
+        Foo {
+            value: 514,
+            info: "world".to_owned(),
+        },
+    );
+    let map = builder.build();
+
+    // registered after the edcode type, but sorted before it by name
+    let sorted: Vec<_> = map.iter_sorted().map(|(ty, _)| ty).collect();
+    assert_eq!(sorted, [persistent_ty, edcode_ty]);
+
+    assert_eq!(
+        fastnbt::to_bytes(&map).expect("serialize failed"),
+        fastnbt::to_bytes(&map.clone()).expect("serialize failed"),
+    );
+}
+
 #[test]
 fn changes_serde() {
     init_registry();
@@ -470,3 +537,236 @@ fn changes_edcode() {
         1919
     );
 }
+
+#[test]
+fn changes_encoded_len_hint() {
+    init_registry();
+    let reg = crate::test_global_integration::registry();
+    let edcode_ty = reg
+        .get(&TYPE_TRANSIENT_EDCODE_KEY)
+        .expect("invalid registry");
+    let persistent_ty = reg.get(&TYPE_PERSISTENT_KEY).expect("invalid registry");
+
+    let mut builder = ComponentChanges::builder();
+    builder.insert(
+        edcode_ty,
+        Foo {
+            value: 114,
+            info: "hello".to_owned(),
+        },
+    );
+    builder.remove(persistent_ty);
+    let changes = builder.build();
+
+    let mut buf = Vec::new();
+    changes.encode(&mut buf).expect("serialize failed");
+    assert_eq!(changes.encoded_len_hint(), buf.len());
+}
+
+#[test]
+fn map_diff_against() {
+    init_registry();
+    let reg = crate::test_global_integration::registry();
+    let edcode_ty = reg
+        .get(&TYPE_TRANSIENT_EDCODE_KEY)
+        .expect("invalid registry");
+    let persistent_ty = reg.get(&TYPE_PERSISTENT_KEY).expect("invalid registry");
+
+    let mut builder = ComponentMap::builder();
+    builder.insert(
+        edcode_ty,
+        Foo {
+            value: 114,
+            info: "hello".to_owned(),
+        },
+    );
+    builder.insert(
+        persistent_ty,
+        Foo {
+            value: 514,
+            info: "world".to_owned(),
+        },
+    );
+    let base = builder.build();
+
+    let mut builder = ComponentMap::builder();
+    builder.insert(
+        persistent_ty,
+        Foo {
+            value: 1919,
+            info: "wlg".to_owned(),
+        },
+    );
+    let map = builder.build();
+
+    assert!(base.diff_against(&base).is_empty());
+
+    let changes = map.diff_against(&base);
+    assert_eq!(changes.len(), 2);
+    assert!(
+        unsafe { changes.get(&TYPE_TRANSIENT_EDCODE) }
+            .expect("missing edcode_ty")
+            .is_none(),
+        "edcode_ty should be removed"
+    );
+    assert_eq!(
+        unsafe { changes.get(&TYPE_PERSISTENT) }
+            .flatten()
+            .expect("missing persistent_ty")
+            .value,
+        1919
+    );
+}
+
+#[test]
+fn map_diff_against_round_trip() {
+    init_registry();
+    let reg = crate::test_global_integration::registry();
+    let edcode_ty = reg
+        .get(&TYPE_TRANSIENT_EDCODE_KEY)
+        .expect("invalid registry");
+    let persistent_ty = reg.get(&TYPE_PERSISTENT_KEY).expect("invalid registry");
+
+    let mut builder = ComponentMap::builder();
+    builder.insert(
+        edcode_ty,
+        Foo {
+            value: 114,
+            info: "hello".to_owned(),
+        },
+    );
+    builder.insert(
+        persistent_ty,
+        Foo {
+            value: 514,
+            info: "world".to_owned(),
+        },
+    );
+    let base = builder.build();
+
+    let mut builder = ComponentMap::builder();
+    builder.insert(
+        persistent_ty,
+        Foo {
+            value: 1919,
+            info: "wlg".to_owned(),
+        },
+    );
+    let map = builder.build();
+
+    let patched = ComponentMap::arc_with_changes(Arc::new(base.clone()), map.diff_against(&base));
+    assert_eq!(patched.len(), map.len());
+    assert_eq!(patched, map);
+
+    let patched = ComponentMap::arc_with_changes(Arc::new(map.clone()), base.diff_against(&map));
+    assert_eq!(patched, base);
+}
+
+#[test]
+fn map_merged() {
+    init_registry();
+    let reg = crate::test_global_integration::registry();
+    let edcode_ty = reg
+        .get(&TYPE_TRANSIENT_EDCODE_KEY)
+        .expect("invalid registry");
+    let persistent_ty = reg.get(&TYPE_PERSISTENT_KEY).expect("invalid registry");
+
+    let mut builder = ComponentMap::builder();
+    builder.insert(
+        edcode_ty,
+        Foo {
+            value: 114,
+            info: "hello".to_owned(),
+        },
+    );
+    builder.insert(
+        persistent_ty,
+        Foo {
+            value: 514,
+            info: "world".to_owned(),
+        },
+    );
+    let base = builder.build();
+
+    let mut builder = ComponentMap::builder();
+    builder.insert(
+        edcode_ty,
+        Foo {
+            value: 1919,
+            info: "wlg".to_owned(),
+        },
+    );
+    let overrides = builder.build();
+
+    let map = base.merged(&overrides);
+    assert_eq!(map.len(), 2);
+    assert_eq!(
+        unsafe { map.get(&TYPE_TRANSIENT_EDCODE) }
+            .expect("missing edcode_ty")
+            .value,
+        1919,
+        "override value should win"
+    );
+    assert_eq!(
+        unsafe { map.get(&TYPE_PERSISTENT) }
+            .expect("missing persistent_ty")
+            .value,
+        514
+    );
+    assert_eq!(base.merged(&ComponentMap::EMPTY), base);
+}
+
+#[test]
+fn map_partition_persistence() {
+    init_registry();
+    let reg = crate::test_global_integration::registry();
+    let edcode_ty = reg
+        .get(&TYPE_TRANSIENT_EDCODE_KEY)
+        .expect("invalid registry");
+    let persistent_ty = reg.get(&TYPE_PERSISTENT_KEY).expect("invalid registry");
+
+    let mut builder = ComponentMap::builder();
+    builder.insert(
+        edcode_ty,
+        Foo {
+            value: 114,
+            info: "hello".to_owned(),
+        },
+    );
+    builder.insert(
+        persistent_ty,
+        Foo {
+            value: 514,
+            info: "world".to_owned(),
+        },
+    );
+    let map = builder.build();
+
+    let (persistent, transient) = map.partition_persistence();
+    assert_eq!(persistent.len(), 1);
+    assert!(persistent.contains(&TYPE_PERSISTENT));
+    assert_eq!(transient.len(), 1);
+    assert!(transient.contains(&TYPE_TRANSIENT_EDCODE));
+}
+
+#[test]
+fn default_map() {
+    const PACKET_CODEC_U8: PacketCodec<'static, u8> = crate::packet_codec_edcode();
+    const TYPE_U8: ComponentType<'static, u8> = ComponentType::<'static, u8>::builder::<Context>()
+        .packet_codec(&PACKET_CODEC_U8)
+        .default_value(&7)
+        .build();
+    assert_eq!(TYPE_U8.default_value(), Some(&7));
+    assert!(TYPE_PERSISTENT.default_value().is_none());
+
+    crate::test_global_integration::peek_registry_mut(|registry| {
+        registry
+            .register(registry_key("u8_default"), (&TYPE_U8).into())
+            .expect("register failed");
+    });
+    init_registry();
+
+    let map = crate::default_map(crate::test_global_integration::registry());
+    assert_eq!(map.len(), 1);
+    assert_eq!(unsafe { map.get(&TYPE_U8) }, Some(&7));
+}
@@ -138,6 +138,37 @@ where
     }
 }
 
+impl<L, T> Palette<L, T>
+where
+    L: Clone,
+    T: Clone + Hash + Eq,
+{
+    /// Creates a palette of the given strategy and bits size holding the entries
+    /// of this palette in the same order, so existing IDs stay valid.
+    ///
+    /// Converting to [`Strategy::Direct`] drops the entries and relies on the
+    /// list, so IDs become raw IDs of the list. A direct palette has no entries
+    /// of its own, so converting it into another strategy yields an empty
+    /// palette.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entries do not fit into the new palette.
+    /// See [`Self::new`].
+    pub fn upgraded_to(&self, strategy: Strategy, bits_size: u32) -> Self {
+        let entries = if strategy == Strategy::Direct {
+            Vec::new()
+        } else {
+            match &self.internal {
+                PaletteImpl::Singular(value) => value.iter().cloned().collect(),
+                PaletteImpl::Array(forward) | PaletteImpl::BiMap { forward, .. } => forward.clone(),
+                PaletteImpl::Direct => Vec::new(),
+            }
+        };
+        Self::new(strategy, bits_size, self.list.clone(), entries)
+    }
+}
+
 impl<L, T> Palette<L, T>
 where
     L: for<'a> IndexToRaw<&'a T>,
@@ -259,6 +290,43 @@ impl<L, T> Palette<L, T> {
         }
     }
 
+    /// Returns an iterator over the IDs and entries of the palette, in the
+    /// order of IDs as accepted by [`Self::get`].
+    ///
+    /// [`Strategy::Direct`] palettes do not hold entries, so the iterator is
+    /// always empty for them.
+    pub fn iter_indexed(&self) -> std::iter::Enumerate<std::slice::Iter<'_, T>> {
+        match &self.internal {
+            PaletteImpl::Singular(value) => value.as_slice(),
+            PaletteImpl::Array(forward) | PaletteImpl::BiMap { forward, .. } => forward,
+            PaletteImpl::Direct => &[],
+        }
+        .iter()
+        .enumerate()
+    }
+
+    /// Returns the maximum number of entries this palette can hold, or `None`
+    /// if it is unbounded.
+    pub fn capacity(&self) -> Option<usize> {
+        match &self.internal {
+            PaletteImpl::Singular(_) => Some(1),
+            PaletteImpl::Array(_) | PaletteImpl::BiMap { .. } => Some(1 << self.index_bits),
+            PaletteImpl::Direct => None,
+        }
+    }
+
+    /// Whether this palette can hold `additional` more entries without being
+    /// resized.
+    pub fn has_capacity_for(&self, additional: usize) -> bool {
+        let len = match &self.internal {
+            PaletteImpl::Singular(value) => value.is_some() as usize,
+            PaletteImpl::Array(forward) | PaletteImpl::BiMap { forward, .. } => forward.len(),
+            PaletteImpl::Direct => return true,
+        };
+        self.capacity()
+            .is_some_and(|capacity| len.saturating_add(additional) <= capacity)
+    }
+
     /// Returns the strategy and the bits size.
     #[inline]
     pub fn config(&self) -> (Strategy, u32) {
@@ -287,12 +355,40 @@ where
     }
 }
 
+/// A seed for decoding a fresh [`Palette`] through `edcode2` crate, carrying
+/// the configuration that is not encoded along with the palette.
+#[cfg(feature = "edcode")]
+#[derive(Debug, Clone)]
+pub struct PaletteSeed<L, T> {
+    /// The strategy of the palette.
+    pub strategy: Strategy,
+    /// The bits size of the palette.
+    pub bits_size: u32,
+    /// The list of entries to index from.
+    pub list: L,
+    _marker: std::marker::PhantomData<fn() -> T>,
+}
+
+#[cfg(feature = "edcode")]
+impl<L, T> PaletteSeed<L, T> {
+    /// Creates a new seed with the given strategy, bits size and list.
+    #[inline]
+    pub const fn new(strategy: Strategy, bits_size: u32, list: L) -> Self {
+        Self {
+            strategy,
+            bits_size,
+            list,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
 #[cfg(feature = "edcode")]
 mod _edcode {
 
     use std::io::{self, ErrorKind};
 
-    use edcode2::{Buf, BufExt, BufMut, BufMutExt, Decode, Encode};
+    use edcode2::{Buf, BufExt, BufMut, BufMutExt, Decode, DecodeSeed, Encode};
 
     use super::*;
 
@@ -325,6 +421,7 @@ mod _edcode {
     impl<'de, L, T, B> Decode<'de, B> for Palette<L, T>
     where
         L: for<'s> IndexFromRaw<'s, T>,
+        T: Clone + Hash + Eq,
         B: Buf,
     {
         fn decode_in_place(&mut self, mut buf: B) -> Result<(), edcode2::BoxedError<'de>> {
@@ -337,13 +434,25 @@ mod _edcode {
                 }
                 PaletteImpl::Array(forward) | PaletteImpl::BiMap { forward, .. } => {
                     let len = buf.get_variable::<u32>() as usize;
-                    *forward = Vec::with_capacity(len);
+                    let capacity = 1 << self.index_bits;
+                    if len > capacity {
+                        return Err(io::Error::new(
+                            ErrorKind::InvalidData,
+                            format!("palette of length {capacity} can't hold {len} entries"),
+                        )
+                        .into());
+                    }
+                    *forward = Vec::with_capacity(capacity);
                     for _ in 0..len {
                         let id = buf.get_variable::<u32>() as usize;
                         forward.push(self.list.of_raw(id).ok_or_else(|| {
                             io::Error::new(ErrorKind::InvalidData, Error::UnknownId(id))
                         })?);
                     }
+                    if let PaletteImpl::BiMap { forward, reverse } = &mut self.internal {
+                        reverse.clear();
+                        reverse.extend(forward.iter().cloned().enumerate().map(|(i, v)| (v, i)));
+                    }
                 }
                 PaletteImpl::Direct => {}
             }
@@ -357,8 +466,26 @@ mod _edcode {
 
         const SUPPORT_NON_IN_PLACE: bool = false;
     }
+
+    impl<'de, L, T, B> DecodeSeed<'de, B> for PaletteSeed<L, T>
+    where
+        L: for<'s> IndexFromRaw<'s, T>,
+        T: Clone + Hash + Eq,
+        B: Buf,
+    {
+        type Output = Palette<L, T>;
+
+        fn decode(self, buf: B) -> Result<Self::Output, edcode2::BoxedError<'de>> {
+            let mut palette = Palette::new(self.strategy, self.bits_size, self.list, Vec::new());
+            palette.decode_in_place(buf)?;
+            Ok(palette)
+        }
+    }
 }
 
+#[cfg(test)]
+mod tests;
+
 /// A trait for types that can be indexed to raw ID.
 pub trait IndexToRaw<T> {
     /// Returns the raw ID of the given entry.
@@ -0,0 +1,123 @@
+use crate::{IndexFromRaw, IndexToRaw, Maybe, Palette, Strategy};
+
+/// A list of entries whose raw IDs are their positions.
+#[derive(Debug, Clone, Copy)]
+struct List(&'static [&'static str]);
+
+impl IndexToRaw<&&'static str> for List {
+    fn raw_id(&self, entry: &&'static str) -> Option<usize> {
+        self.0.iter().position(|e| e == entry)
+    }
+}
+
+impl<'s> IndexFromRaw<'s, Maybe<'s, &'static str>> for List {
+    fn of_raw(&'s self, id: usize) -> Option<Maybe<'s, &'static str>> {
+        self.0.get(id).map(Maybe::Borrowed)
+    }
+}
+
+impl<'s> IndexFromRaw<'s, &'static str> for List {
+    fn of_raw(&'s self, id: usize) -> Option<&'static str> {
+        self.0.get(id).copied()
+    }
+}
+
+const LIST: List = List(&["air", "stone", "dirt", "grass"]);
+
+#[test]
+fn upgraded_to() {
+    let array = Palette::new(Strategy::Array, 2, LIST, vec!["dirt", "air", "stone"]);
+    let bimap = array.upgraded_to(Strategy::BiMap, 4);
+    assert_eq!(bimap.config(), (Strategy::BiMap, 4));
+    for entry in ["dirt", "air", "stone"] {
+        assert_eq!(bimap.index(&entry), array.index(&entry));
+    }
+    assert_eq!(bimap.index(&"grass"), None);
+
+    let direct = bimap.upgraded_to(Strategy::Direct, 0);
+    assert_eq!(direct.config(), (Strategy::Direct, 0));
+    assert_eq!(direct.index(&"dirt"), Some(2));
+
+    let singular = Palette::new(Strategy::Singular, 0, LIST, vec!["grass"]);
+    let array = singular.upgraded_to(Strategy::Array, 1);
+    assert_eq!(array.index(&"grass"), Some(0));
+}
+
+#[test]
+fn capacity() {
+    let mut array = Palette::new(Strategy::Array, 4, LIST, vec![]);
+    assert_eq!(array.capacity(), Some(16));
+    assert!(array.has_capacity_for(16));
+    assert!(!array.has_capacity_for(17));
+
+    for entry in [
+        "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13", "14", "15",
+    ] {
+        assert!(array.has_capacity_for(1));
+        array.index_or_insert(entry).unwrap();
+    }
+    assert!(!array.has_capacity_for(1));
+    assert!(array.has_capacity_for(0));
+    assert!(array.index_or_insert("full").is_err());
+
+    let singular = Palette::new(Strategy::Singular, 0, LIST, vec!["air"]);
+    assert_eq!(singular.capacity(), Some(1));
+    assert!(!singular.has_capacity_for(1));
+
+    let direct: Palette<_, &str> = Palette::new(Strategy::Direct, 0, LIST, vec![]);
+    assert_eq!(direct.capacity(), None);
+    assert!(direct.has_capacity_for(usize::MAX));
+}
+
+#[test]
+fn iter_indexed() {
+    let bimap = Palette::new(Strategy::BiMap, 4, LIST, vec!["dirt", "air", "stone"]);
+    let indexed: Vec<_> = bimap.iter_indexed().collect();
+    assert_eq!(indexed, [(0, &"dirt"), (1, &"air"), (2, &"stone")]);
+    for (id, entry) in indexed {
+        assert_eq!(bimap.get(id).as_deref(), Some(entry));
+    }
+
+    let singular = Palette::new(Strategy::Singular, 0, LIST, vec!["grass"]);
+    assert_eq!(singular.iter_indexed().collect::<Vec<_>>(), [(0, &"grass")]);
+
+    let direct: Palette<_, &str> = Palette::new(Strategy::Direct, 0, LIST, vec![]);
+    assert_eq!(direct.iter_indexed().count(), 0);
+}
+
+#[test]
+#[cfg(feature = "edcode")]
+fn decode_seed() {
+    use edcode2::{DecodeSeed, Encode};
+
+    use crate::PaletteSeed;
+
+    let array = Palette::new(Strategy::Array, 2, LIST, vec!["dirt", "air", "stone"]);
+    let mut buf = Vec::new();
+    array.encode(&mut buf).unwrap();
+
+    let decoded = PaletteSeed::new(Strategy::Array, 2, LIST)
+        .decode(&buf[..])
+        .unwrap();
+    assert_eq!(decoded.config(), array.config());
+    assert!(decoded.iter_indexed().eq(array.iter_indexed()));
+
+    for strategy in [Strategy::Array, Strategy::BiMap] {
+        let mut decoded = PaletteSeed::new(strategy, 4, LIST)
+            .decode(&buf[..])
+            .unwrap();
+        assert_eq!(decoded.config(), (strategy, 4));
+        assert!(decoded.iter_indexed().eq(array.iter_indexed()));
+        for (id, entry) in array.iter_indexed() {
+            assert_eq!(decoded.index(entry), Some(id));
+        }
+        assert_eq!(decoded.capacity(), Some(16));
+        assert!(decoded.has_capacity_for(13));
+        assert_eq!(decoded.index_or_insert("grass"), Ok(3));
+        assert_eq!(decoded.index(&"grass"), Some(3));
+    }
+
+    assert!(PaletteSeed::new(Strategy::Array, 1, LIST)
+        .decode(&buf[..])
+        .is_err());
+}
@@ -7,7 +7,7 @@
 use std::{
     collections::{HashMap, HashSet},
     fmt::Display,
-    hash::Hash,
+    hash::{DefaultHasher, Hash, Hasher},
     ops::{Deref, Index},
     sync::OnceLock,
 };
@@ -38,6 +38,8 @@ pub struct Registry<K, T> {
     entries: Vec<RefEntry<K, T>>,
     kv: HashMap<K, usize>,
     tv: RwLock<HashMap<TagKey<K, T>, Vec<usize>>>,
+    /// Raw ids by hashes of entry values, built on first lookup by value.
+    vk: OnceLock<HashMap<u64, Vec<usize>>>,
 
     /// The default registration raw id.
     default: Option<usize>,
@@ -57,6 +59,12 @@ pub struct Registry<K, T> {
 /// serialize the **raw ID** of the entry.
 ///
 /// This corresponds to the `compressed` option in *Mojang Serialization*.
+///
+/// # Equality and Hashing
+///
+/// Two references are equal only if they point to the same entry of the same registry
+/// instance. The hash only covers the raw id, so references from different registries
+/// with the same raw id are distinct, but collide in hash.
 pub struct Reg<'a, K, T> {
     raw: usize,
     registry: &'a Registry<K, T>,
@@ -81,6 +89,14 @@ where
         })
     }
 
+    /// Gets an entry with the given owned key.
+    ///
+    /// This is a by-value shorthand of [`Self::get`] for keys created inline.
+    #[inline]
+    pub fn get_owned(&self, key: K) -> Option<Reg<'_, K, T>> {
+        self.get(&key)
+    }
+
     /// Whether this registry contains the given key.
     #[inline]
     pub fn contains<Q>(&self, key: &Q) -> bool
@@ -90,7 +106,21 @@ where
         self.kv.contains_key(key.as_key(&self.key))
     }
 
+    /// Gets raw ids of the given keys, in input order.
+    ///
+    /// Keys that are absent from this registry yield `None`.
+    pub fn raw_ids_of<'a, I>(&'a self, keys: I) -> impl Iterator<Item = Option<usize>> + 'a
+    where
+        I: IntoIterator<Item = &'a K>,
+        I::IntoIter: 'a,
+    {
+        keys.into_iter().map(|key| self.kv.get(key).copied())
+    }
+
     /// Gets entries of given tag.
+    ///
+    /// The entries are snapshotted, so tags of this registry can be modified
+    /// while iterating.
     pub fn of_tag<'a>(&'a self, tag: &TagKey<K, T>) -> OfTag<'a, K, T> {
         OfTag {
             inner: self
@@ -103,6 +133,59 @@ where
             registry: self,
         }
     }
+
+    /// Picks an entry of given tag uniformly at random.
+    ///
+    /// Returns `None` if the tag is absent or has no entries.
+    #[cfg(feature = "rand")]
+    pub fn random_of_tag<R>(&self, tag: &TagKey<K, T>, rng: &mut R) -> Option<Reg<'_, K, T>>
+    where
+        R: rand::Rng + ?Sized,
+    {
+        let raw = {
+            let tv = self.tv.read();
+            let raws = tv.get(tag).filter(|raws| !raws.is_empty())?;
+            raws.get(rng.random_range(..raws.len())).copied()?
+        };
+        self.of_raw(raw)
+    }
+
+    /// Gets the number of entries of given tag.
+    #[inline]
+    pub fn tag_len(&self, tag: &TagKey<K, T>) -> usize {
+        self.tv.read().get(tag).map_or(0, Vec::len)
+    }
+}
+
+impl<K, T> Registry<K, T>
+where
+    T: Hash + Eq,
+{
+    /// Gets the entry with the given value.
+    ///
+    /// If multiple entries have equal values, the one with the lowest raw id
+    /// is returned. The reverse index is built on the first call.
+    pub fn get_by_value<'a>(&'a self, value: &T) -> Option<Reg<'a, K, T>> {
+        let vk = self.vk.get_or_init(|| {
+            let mut vk: HashMap<u64, Vec<usize>> = HashMap::new();
+            for entry in &self.entries {
+                if let Some(value) = entry.value() {
+                    vk.entry(value_hash(value)).or_default().push(entry.raw);
+                }
+            }
+            vk
+        });
+        vk.get(&value_hash(value))?
+            .iter()
+            .find_map(|&raw| self.of_raw(raw).filter(|reg| reg.value == value))
+    }
+}
+
+#[inline]
+fn value_hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
 }
 
 impl<K, T> Registry<K, T> {
@@ -167,6 +250,26 @@ impl<K, T> Registry<K, T> {
     pub fn default_entry(&self) -> Option<Reg<'_, K, T>> {
         self.default.and_then(|raw| self.of_raw(raw))
     }
+
+    /// Gets all entries of this registry, yielding the default entry first
+    /// and then the rest in raw id order.
+    pub fn entries_default_first(&self) -> impl Iterator<Item = Reg<'_, K, T>> + '_ {
+        self.default_entry().into_iter().chain(
+            self.entries()
+                .filter(|entry| Some(entry.raw) != self.default),
+        )
+    }
+
+    /// Leaks this registry, so references obtained from it are `'static`.
+    ///
+    /// # Memory Leaks
+    ///
+    /// The registry is **never** dropped, along with all its entries and tags.
+    /// This should only be used for registries that live for the rest of the program.
+    #[inline]
+    pub fn leak(self) -> &'static Self {
+        Box::leak(Box::new(self))
+    }
 }
 
 impl<K, T, Q> Index<Q> for Registry<K, T>
@@ -215,6 +318,17 @@ impl<'a, K, T> Reg<'a, K, T> {
     }
 }
 
+impl<K, T> Reg<'_, K, T>
+where
+    K: Hash + Eq,
+{
+    /// Whether this entry is bound to the given tag.
+    #[inline]
+    pub fn in_tag(this: Self, tag: &TagKey<K, T>) -> bool {
+        <&RefEntry<_, _>>::from(this).tags.read().contains(tag)
+    }
+}
+
 impl<'a, K, T> From<Reg<'a, K, T>> for &'a RefEntry<K, T> {
     #[inline]
     fn from(value: Reg<'a, K, T>) -> Self {
@@ -259,7 +373,7 @@ impl<K, T> Deref for Reg<'_, K, T> {
 
 impl<K, T> Hash for Reg<'_, K, T> {
     #[inline]
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+    fn hash<H: Hasher>(&self, state: &mut H) {
         self.raw.hash(state)
     }
 }
@@ -267,7 +381,7 @@ impl<K, T> Hash for Reg<'_, K, T> {
 impl<K, T> PartialEq for Reg<'_, K, T> {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
-        self.raw == other.raw
+        self.raw == other.raw && std::ptr::eq(self.registry, other.registry)
     }
 }
 
@@ -427,8 +541,10 @@ impl<'a, K, T> IntoIterator for &'a Registry<K, T> {
 #[derive(Debug)]
 pub struct RegistryMut<K, T> {
     key: Key<K, Registry<K, T>>,
-    entries: Vec<(T, RefEntry<K, T>)>,
-    keys: OnceLock<HashSet<K>>,
+    /// Entries by raw id, with `None` as tombstones of removed entries.
+    entries: Vec<Option<(T, RefEntry<K, T>)>>,
+    /// Raw ids by keys, excluding removed entries.
+    keys: OnceLock<HashMap<K, usize>>,
 
     default: Option<usize>,
 }
@@ -450,6 +566,22 @@ impl<K, T> RegistryMut<K, T> {
     pub fn key(&self) -> &Key<K, Registry<K, T>> {
         &self.key
     }
+
+    /// Gets the key of the default entry of this registry.
+    #[inline]
+    pub fn default_key(&self) -> Option<&Key<K, T>> {
+        self.default
+            .and_then(|raw| self.entries[raw].as_ref())
+            .map(|(_, entry)| &entry.key)
+    }
+
+    /// Clears the default entry designation of this registry.
+    ///
+    /// The entry itself stays registered.
+    #[inline]
+    pub fn clear_default(&mut self) {
+        self.default = None;
+    }
 }
 
 impl<K, T> RegistryMut<K, T>
@@ -458,21 +590,25 @@ where
 {
     /// Registers a new entry and returns its raw id if successful.
     ///
+    /// The returned raw id counts the tombstones of removed entries, so it is
+    /// shifted when this registry is frozen if any entry has been removed.
+    /// See [`Self::remove`].
+    ///
     /// # Errors
     ///
     /// Returns back the given key and value if registration with the key already exists.
     #[allow(clippy::missing_panics_doc)]
     pub fn register(&mut self, key: Key<K, T>, value: T) -> Result<usize, (Key<K, T>, T)> {
         if self.keys.get_mut().is_none() {
-            self.keys = HashSet::new().into();
+            self.keys = HashMap::new().into();
         }
         let keys = self.keys.get_mut().expect("keys not initialized");
-        if keys.contains(key.value()) {
+        if keys.contains_key(key.value()) {
             return Err((key, value));
         }
-        keys.insert(key.value().clone());
         let raw = self.entries.len();
-        self.entries.push((
+        keys.insert(key.value().clone(), raw);
+        self.entries.push(Some((
             value,
             RefEntry {
                 raw,
@@ -480,10 +616,67 @@ where
                 value: None,
                 tags: RwLock::new(HashSet::new()),
             },
-        ));
+        )));
         Ok(raw)
     }
 
+    /// Gets the raw id of the entry with the given key, registering the value
+    /// created by `value_fn` if there is no such entry.
+    ///
+    /// The default entry designation is never changed by this function.
+    pub fn get_or_register<F>(&mut self, key: Key<K, T>, value_fn: F) -> usize
+    where
+        F: FnOnce() -> T,
+    {
+        if let Some(raw) = self.raw_of(&key) {
+            return raw;
+        }
+        let raw = self.entries.len();
+        let registered = self.register(key, value_fn());
+        debug_assert_eq!(
+            registered.ok(),
+            Some(raw),
+            "key registered without an entry"
+        );
+        raw
+    }
+
+    /// Removes the entry with the given key and returns its value.
+    ///
+    /// The raw id of the removed entry is left as a tombstone, so raw ids of other
+    /// entries stay unchanged until this registry is frozen, where the raw ids
+    /// of following entries are shifted to fill the gap.
+    /// If the removed entry is the default entry, the default designation is
+    /// cleared.
+    pub fn remove(&mut self, key: &Key<K, T>) -> Option<T> {
+        let raw = self.keys.get_mut()?.remove(key.value())?;
+        if self.default == Some(raw) {
+            self.default = None;
+        }
+        self.entries[raw].take().map(|(value, _)| value)
+    }
+
+    /// Replaces the value of the entry with the given key, keeping its raw id,
+    /// and returns the old value.
+    ///
+    /// The entry is registered if there is no entry with the key.
+    pub fn replace(&mut self, key: Key<K, T>, value: T) -> Option<T> {
+        match self.raw_of(&key) {
+            Some(raw) => self.entries[raw]
+                .as_mut()
+                .map(|(old, _)| std::mem::replace(old, value)),
+            None => {
+                let registered = self.register(key, value);
+                debug_assert!(registered.is_ok(), "key registered without an entry");
+                None
+            }
+        }
+    }
+
+    fn raw_of(&self, key: &Key<K, T>) -> Option<usize> {
+        self.keys.get()?.get(key.value()).copied()
+    }
+
     /// Registers a unique default entry of this registry.
     ///
     /// See [`Self::register`].
@@ -503,10 +696,16 @@ where
     K: Hash + Eq + Clone,
 {
     fn from(value: RegistryMut<K, T>) -> Self {
+        let default = value
+            .default
+            .map(|raw| value.entries[..raw].iter().flatten().count());
         let entries: Vec<_> = value
             .entries
             .into_iter()
-            .map(|(v, mut r)| {
+            .flatten()
+            .enumerate()
+            .map(|(raw, (v, mut r))| {
+                r.raw = raw;
                 r.value = Some(v);
                 r
             })
@@ -519,12 +718,35 @@ where
                 .map(|(raw, entry)| (entry.key.value().clone(), raw))
                 .collect(),
             tv: RwLock::new(HashMap::new()),
+            vk: OnceLock::new(),
             entries,
-            default: value.default,
+            default,
         }
     }
 }
 
+/// Differences between the keys of two registries.
+///
+/// See [`Registry::diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistryDiff<K> {
+    /// Keys only present in the other registry, ordered by their raw ids there.
+    pub added: Vec<K>,
+    /// Keys only present in this registry, ordered by their raw ids here.
+    pub removed: Vec<K>,
+    /// Keys present in both registries under different raw ids, along with
+    /// their raw ids here and there.
+    pub remapped: Vec<(K, usize, usize)>,
+}
+
+impl<K> RegistryDiff<K> {
+    /// Whether the two registries have the same keys under the same raw ids.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.remapped.is_empty()
+    }
+}
+
 /// Trait for providing a registry.
 #[deprecated = "use local-cx to obtain registry instead"]
 pub trait ProvideRegistry<'r, K, T> {
@@ -537,6 +759,9 @@ where
     K: Hash + Eq + Clone,
 {
     /// Binds given tags to entries, and removes old tag bindings.
+    ///
+    /// All entries should belong to this registry, which is only checked
+    /// in debug builds. See [`Self::try_populate_tags`] for a checked version.
     #[doc(alias = "bind_tags")]
     pub fn populate_tags<'a, I>(&'a self, entries: I)
     where
@@ -548,6 +773,7 @@ where
         let mut tv = self.tv.write();
         for (tag, entries) in iter {
             for entry in entries.iter() {
+                debug_assert!(self.owns(entry), "entry does not belong to this registry");
                 entry.tags.write().insert(tag.clone());
             }
             if let Some(vec) = tv.get_mut(&tag) {
@@ -558,6 +784,77 @@ where
         }
     }
 
+    /// Binds given tags to entries like [`Self::populate_tags`], checking that
+    /// all entries belong to this registry first.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first entry not belonging to this registry, leaving the
+    /// tag bindings untouched.
+    #[doc(alias = "try_bind_tags")]
+    pub fn try_populate_tags<'a, I>(&'a self, entries: I) -> Result<(), &'a RefEntry<K, T>>
+    where
+        I: IntoIterator<Item = (TagKey<K, T>, Vec<&'a RefEntry<K, T>>)>,
+    {
+        let entries: Vec<_> = entries.into_iter().collect();
+        if let Some(entry) = entries
+            .iter()
+            .flat_map(|(_, entries)| entries)
+            .find(|entry| !self.owns(entry))
+        {
+            return Err(entry);
+        }
+        self.populate_tags(entries);
+        Ok(())
+    }
+
+    /// Whether the given entry belongs to this registry instance.
+    #[inline]
+    fn owns(&self, entry: &RefEntry<K, T>) -> bool {
+        self.entries
+            .get(entry.raw)
+            .is_some_and(|e| std::ptr::eq(e, entry))
+    }
+
+    /// Gets all entries of this registry along with their bound tags, in raw id
+    /// order.
+    ///
+    /// Tags of each entry are cloned when the entry is reached.
+    pub fn entries_with_tags(&self) -> impl Iterator<Item = (Reg<'_, K, T>, Vec<TagKey<K, T>>)> {
+        self.entries().map(|reg| {
+            let tags = reg.as_ref().tags.read().iter().cloned().collect();
+            (reg, tags)
+        })
+    }
+
+    /// Compares the keys of this registry against another registry, describing
+    /// how to get from this registry to the other.
+    ///
+    /// All lists of the diff are ordered by raw id, so the diff is stable.
+    pub fn diff(&self, other: &Self) -> RegistryDiff<K> {
+        let added = other
+            .entries
+            .iter()
+            .filter(|entry| !self.kv.contains_key(entry.key.value()))
+            .map(|entry| entry.key.value().clone())
+            .collect();
+        let mut removed = Vec::new();
+        let mut remapped = Vec::new();
+        for entry in &self.entries {
+            let key = entry.key.value();
+            match other.kv.get(key) {
+                None => removed.push(key.clone()),
+                Some(&raw) if raw != entry.raw => remapped.push((key.clone(), entry.raw, raw)),
+                Some(_) => {}
+            }
+        }
+        RegistryDiff {
+            added,
+            removed,
+            remapped,
+        }
+    }
+
     /// Clears all tags.
     pub fn clear_tags(&self) {
         for entry in self.entries.iter() {
@@ -565,6 +862,26 @@ where
         }
         self.tv.write().clear();
     }
+
+    /// Converts this registry back into a mutable registry, for registering more entries.
+    ///
+    /// Raw ids of existing entries are preserved, while tag bindings are dropped.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn into_mut(self) -> RegistryMut<K, T> {
+        RegistryMut {
+            key: self.key,
+            entries: self
+                .entries
+                .into_iter()
+                .map(|mut r| {
+                    r.tags.get_mut().clear();
+                    Some((r.value.take().expect("registry entry without value"), r))
+                })
+                .collect(),
+            keys: OnceLock::from(self.kv),
+            default: self.default,
+        }
+    }
 }
 
 #[cfg(feature = "serde")]
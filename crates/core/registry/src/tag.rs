@@ -66,6 +66,26 @@ impl<K, T> Tags<'_, K, T> {
             registry: self.registry,
         }
     }
+
+    /// Gets the number of tags.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Whether there are no tags.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+impl<K: Hash + Eq, T> Tags<'_, K, T> {
+    /// Whether the given tag is present.
+    #[inline]
+    pub fn contains(&self, tag: &TagKey<K, T>) -> bool {
+        self.inner.contains_key(tag)
+    }
 }
 
 impl<'a: 'a, K, T> IntoIterator for &'a Tags<'_, K, T> {
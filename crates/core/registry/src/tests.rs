@@ -38,3 +38,455 @@ fn freeze() {
     assert_eq!(registry.get(&"two").unwrap(), 2);
     assert!(registry.get(&"three").is_none());
 }
+
+#[test]
+fn raw_ids_of() {
+    let mut registry: RegistryMut<&'static str, i32> =
+        RegistryMut::new(Key::new("root", "integer"));
+
+    assert!(registry
+        .register(Key::new(registry.key().value(), "one"), 1)
+        .is_ok());
+    assert!(registry
+        .register(Key::new(registry.key().value(), "two"), 2)
+        .is_ok());
+
+    let registry: Registry<_, _> = registry.into();
+
+    assert_eq!(
+        registry
+            .raw_ids_of(&["two", "three", "one"])
+            .collect::<Vec<_>>(),
+        [Some(1), None, Some(0)]
+    );
+}
+
+#[test]
+fn reg_eq_across_registries() {
+    let mut a: RegistryMut<&'static str, i32> = RegistryMut::new(Key::new("root", "a"));
+    let mut b: RegistryMut<&'static str, i32> = RegistryMut::new(Key::new("root", "b"));
+    assert!(a.register(Key::new(a.key().value(), "one"), 1).is_ok());
+    assert!(b.register(Key::new(b.key().value(), "one"), 1).is_ok());
+
+    let a: Registry<_, _> = a.into();
+    let b: Registry<_, _> = b.into();
+
+    assert_eq!(a.get(&"one"), a.get(&"one"));
+    assert_ne!(a.get(&"one"), b.get(&"one"));
+}
+
+#[test]
+fn into_mut() {
+    let mut registry: RegistryMut<&'static str, i32> =
+        RegistryMut::new(Key::new("root", "integer"));
+
+    assert!(registry
+        .register(Key::new(registry.key().value(), "one"), 1)
+        .is_ok());
+
+    let mut registry = Registry::from(registry).into_mut();
+    assert!(registry
+        .register(Key::new(registry.key().value(), "one"), 1)
+        .is_err());
+    assert_eq!(
+        registry.register(Key::new(registry.key().value(), "two"), 2),
+        Ok(1)
+    );
+
+    let registry: Registry<_, _> = registry.into();
+    assert_eq!(Reg::raw_id(registry.get(&"one").unwrap()), 0);
+    assert_eq!(registry.get(&"two").unwrap(), 2);
+}
+
+#[test]
+fn into_mut_round_trip() {
+    let mut registry: RegistryMut<&'static str, i32> =
+        RegistryMut::new(Key::new("root", "integer"));
+    for (key, value) in [("one", 1), ("two", 2)] {
+        assert!(registry
+            .register(Key::new(registry.key().value(), key), value)
+            .is_ok());
+    }
+    assert!(registry
+        .register_default(Key::new(registry.key().value(), "zero"), 0)
+        .is_ok());
+
+    let keys = |registry: &Registry<&'static str, i32>| {
+        registry
+            .entries()
+            .map(|reg| {
+                (
+                    Reg::raw_id(reg),
+                    *<&RefEntry<_, _>>::from(reg).key().value(),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    let registry: Registry<_, _> = registry.into();
+    let before = keys(&registry);
+    let registry: Registry<_, _> = registry.into_mut().into();
+
+    assert_eq!(keys(&registry), before);
+    assert_eq!(before, [(0, "one"), (1, "two"), (2, "zero")]);
+    assert_eq!(registry.default_entry().map(|reg| *reg), Some(0));
+}
+
+#[test]
+fn default_key() {
+    let mut registry: RegistryMut<&'static str, i32> =
+        RegistryMut::new(Key::new("root", "integer"));
+    assert!(registry.default_key().is_none());
+
+    assert!(registry
+        .register_default(Key::new(registry.key().value(), "zero"), 0)
+        .is_ok());
+    assert!(registry
+        .register_default(Key::new(registry.key().value(), "one"), 1)
+        .is_err());
+    assert_eq!(registry.default_key().map(Key::value), Some(&"zero"));
+
+    registry.clear_default();
+    assert!(registry.default_key().is_none());
+    assert!(registry
+        .register_default(Key::new(registry.key().value(), "one"), 1)
+        .is_ok());
+    assert_eq!(registry.default_key().map(Key::value), Some(&"one"));
+}
+
+#[test]
+fn leak() {
+    let mut registry: RegistryMut<&'static str, i32> =
+        RegistryMut::new(Key::new("root", "integer"));
+    assert!(registry
+        .register(Key::new(registry.key().value(), "one"), 1)
+        .is_ok());
+
+    let registry = Registry::from(registry).leak();
+    let entry: Reg<'static, _, _> = registry.get(&"one").unwrap();
+    assert_eq!(entry, 1);
+}
+
+#[test]
+fn in_tag() {
+    let mut registry: RegistryMut<&'static str, i32> =
+        RegistryMut::new(Key::new("root", "integer"));
+    assert!(registry
+        .register(Key::new(registry.key().value(), "one"), 1)
+        .is_ok());
+    assert!(registry
+        .register(Key::new(registry.key().value(), "two"), 2)
+        .is_ok());
+
+    let registry: Registry<_, _> = registry.into();
+    let odd = TagKey {
+        registry: *registry.key(),
+        id: "odd",
+    };
+    registry.populate_tags([(
+        odd,
+        vec![<&RefEntry<_, _>>::from(registry.get(&"one").unwrap())],
+    )]);
+
+    assert!(Reg::in_tag(registry.get(&"one").unwrap(), &odd));
+    assert!(!Reg::in_tag(registry.get(&"two").unwrap(), &odd));
+}
+
+#[test]
+fn tags_len_contains() {
+    let mut registry: RegistryMut<&'static str, i32> =
+        RegistryMut::new(Key::new("root", "integer"));
+    assert!(registry
+        .register(Key::new(registry.key().value(), "one"), 1)
+        .is_ok());
+
+    let registry: Registry<_, _> = registry.into();
+    let odd = TagKey {
+        registry: *registry.key(),
+        id: "odd",
+    };
+    let even = TagKey {
+        registry: *registry.key(),
+        id: "even",
+    };
+    assert!(registry.tags().is_empty());
+
+    registry.populate_tags([(
+        odd,
+        vec![<&RefEntry<_, _>>::from(registry.get(&"one").unwrap())],
+    )]);
+
+    let tags = registry.tags();
+    assert_eq!(tags.len(), 1);
+    assert!(tags.contains(&odd));
+    assert!(!tags.contains(&even));
+}
+
+#[test]
+fn tag_len() {
+    let mut registry: RegistryMut<&'static str, i32> =
+        RegistryMut::new(Key::new("root", "integer"));
+    for (key, value) in [("one", 1), ("two", 2), ("three", 3)] {
+        assert!(registry
+            .register(Key::new(registry.key().value(), key), value)
+            .is_ok());
+    }
+
+    let registry: Registry<_, _> = registry.into();
+    let all = TagKey {
+        registry: *registry.key(),
+        id: "all",
+    };
+    let none = TagKey {
+        registry: *registry.key(),
+        id: "none",
+    };
+    registry.populate_tags([(
+        all,
+        registry.entries().map(<&RefEntry<_, _>>::from).collect(),
+    )]);
+
+    assert_eq!(registry.tag_len(&all), 3);
+    assert_eq!(registry.tag_len(&none), 0);
+    assert_eq!(registry.of_tag(&all).size_hint(), (3, Some(3)));
+    assert_eq!(registry.of_tag(&all).map(|reg| *reg).sum::<i32>(), 6);
+    assert_eq!(registry.of_tag(&none).count(), 0);
+
+    let mut iter = registry.of_tag(&all);
+    assert!(iter.next().is_some());
+    registry.clear_tags();
+    assert_eq!(registry.tag_len(&all), 0);
+    assert_eq!(iter.count(), 2);
+}
+
+#[test]
+#[cfg(feature = "rand")]
+fn random_of_tag() {
+    use rand::{rngs::StdRng, SeedableRng as _};
+
+    let mut registry: RegistryMut<&'static str, i32> =
+        RegistryMut::new(Key::new("root", "integer"));
+    for (key, value) in [("one", 1), ("two", 2), ("three", 3)] {
+        assert!(registry
+            .register(Key::new(registry.key().value(), key), value)
+            .is_ok());
+    }
+
+    let registry: Registry<_, _> = registry.into();
+    let all = TagKey {
+        registry: *registry.key(),
+        id: "all",
+    };
+    let empty = TagKey {
+        registry: *registry.key(),
+        id: "empty",
+    };
+    let none = TagKey {
+        registry: *registry.key(),
+        id: "none",
+    };
+    registry.populate_tags([
+        (
+            all,
+            registry.entries().map(<&RefEntry<_, _>>::from).collect(),
+        ),
+        (empty, vec![]),
+    ]);
+
+    let mut rng = StdRng::seed_from_u64(0);
+    assert_eq!(
+        registry.random_of_tag(&all, &mut rng).map(|reg| *reg),
+        Some(3)
+    );
+    assert!(registry.random_of_tag(&empty, &mut rng).is_none());
+    assert!(registry.random_of_tag(&none, &mut rng).is_none());
+}
+
+#[test]
+fn entries_default_first() {
+    let mut registry: RegistryMut<&'static str, i32> =
+        RegistryMut::new(Key::new("root", "integer"));
+    for (id, value) in [("one", 1), ("two", 2)] {
+        assert!(registry
+            .register(Key::new(registry.key().value(), id), value)
+            .is_ok());
+    }
+    assert!(registry
+        .register_default(Key::new(registry.key().value(), "zero"), 0)
+        .is_ok());
+
+    let registry: Registry<_, _> = registry.into();
+    assert_eq!(
+        registry
+            .entries_default_first()
+            .map(|entry| *entry)
+            .collect::<Vec<_>>(),
+        [0, 1, 2]
+    );
+}
+
+#[test]
+fn try_populate_tags() {
+    let mut registry: RegistryMut<&'static str, i32> =
+        RegistryMut::new(Key::new("root", "integer"));
+    assert!(registry
+        .register(Key::new(registry.key().value(), "one"), 1)
+        .is_ok());
+    let registry: Registry<_, _> = registry.into();
+
+    let mut other: RegistryMut<&'static str, i32> = RegistryMut::new(Key::new("root", "integer"));
+    assert!(other
+        .register(Key::new(other.key().value(), "one"), 1)
+        .is_ok());
+    let other: Registry<_, _> = other.into();
+
+    let odd = TagKey {
+        registry: *registry.key(),
+        id: "odd",
+    };
+    let ours = <&RefEntry<_, _>>::from(registry.get(&"one").unwrap());
+    let theirs = <&RefEntry<_, _>>::from(other.get(&"one").unwrap());
+
+    assert!(registry
+        .try_populate_tags([(odd, vec![ours, theirs])])
+        .is_err());
+    assert!(registry.tags().is_empty());
+
+    assert!(registry.try_populate_tags([(odd, vec![ours])]).is_ok());
+    assert!(registry.tags().contains(&odd));
+}
+
+#[test]
+fn get_owned() {
+    let mut registry: RegistryMut<String, i32> =
+        RegistryMut::new(Key::new("root".to_owned(), "integer".to_owned()));
+    assert!(registry
+        .register(
+            Key::new(registry.key().value().clone(), "one".to_owned()),
+            1
+        )
+        .is_ok());
+
+    let registry: Registry<_, _> = registry.into();
+    assert_eq!(registry.get_owned("one".to_owned()).map(|e| *e), Some(1));
+    assert!(registry.get_owned("two".to_owned()).is_none());
+}
+
+#[test]
+fn remove_replace() {
+    let key = |value| Key::new("integer", value);
+    let mut registry: RegistryMut<&'static str, i32> =
+        RegistryMut::new(Key::new("root", "integer"));
+
+    registry.register(key("one"), 1).unwrap();
+    registry.register(key("two"), 2).unwrap();
+    registry.register_default(key("three"), 3).unwrap();
+
+    assert_eq!(registry.remove(&key("two")), Some(2));
+    assert_eq!(registry.remove(&key("two")), None);
+    assert_eq!(registry.default_key(), Some(&key("three")));
+
+    assert_eq!(registry.replace(key("three"), 33), Some(3));
+    assert_eq!(registry.replace(key("four"), 4), None);
+
+    let registry: Registry<_, _> = registry.into();
+    assert_eq!(registry.len(), 3);
+    assert!(registry.get(&"two").is_none());
+    assert_eq!(registry.get(&"one").unwrap(), 1);
+    assert_eq!(registry.get(&"three").unwrap(), 33);
+    assert_eq!(Reg::raw_id(registry.get(&"three").unwrap()), 1);
+    assert_eq!(Reg::raw_id(registry.get(&"four").unwrap()), 2);
+    assert_eq!(registry.default_entry().unwrap(), 33);
+
+    let mut registry = registry.into_mut();
+    assert_eq!(registry.remove(&key("three")), Some(33));
+    assert!(registry.default_key().is_none());
+}
+
+#[test]
+fn get_by_value() {
+    let key = |value| Key::new("integer", value);
+    let mut registry: RegistryMut<&'static str, i32> =
+        RegistryMut::new(Key::new("root", "integer"));
+
+    registry.register(key("one"), 1).unwrap();
+    registry.register(key("two"), 2).unwrap();
+    registry.register(key("another_one"), 1).unwrap();
+
+    let registry: Registry<_, _> = registry.into();
+    assert_eq!(*Reg::id(registry.get_by_value(&1).unwrap()), "one");
+    assert_eq!(*Reg::id(registry.get_by_value(&2).unwrap()), "two");
+    assert!(registry.get_by_value(&3).is_none());
+}
+
+#[test]
+fn entries_with_tags() {
+    let key = |value| Key::new("integer", value);
+    let mut registry: RegistryMut<&'static str, i32> =
+        RegistryMut::new(Key::new("root", "integer"));
+    registry.register(key("one"), 1).unwrap();
+    registry.register(key("two"), 2).unwrap();
+    let registry: Registry<_, _> = registry.into();
+
+    let tag = |id| TagKey {
+        registry: *registry.key(),
+        id,
+    };
+    let one = <&RefEntry<_, _>>::from(registry.get(&"one").unwrap());
+    registry.populate_tags([(tag("odd"), vec![one]), (tag("positive"), vec![one])]);
+
+    let entries: Vec<_> = registry.entries_with_tags().collect();
+    assert_eq!(entries.len(), 2);
+    let (reg, tags) = &entries[0];
+    assert_eq!(*Reg::id(*reg), "one");
+    assert_eq!(tags.len(), 2);
+    assert!(tags.contains(&tag("odd")));
+    assert!(tags.contains(&tag("positive")));
+    let (reg, tags) = &entries[1];
+    assert_eq!(*Reg::id(*reg), "two");
+    assert!(tags.is_empty());
+}
+
+#[test]
+fn diff() {
+    let key = |value| Key::new("integer", value);
+    let build = |keys: &[&'static str]| -> Registry<&'static str, i32> {
+        let mut registry = RegistryMut::new(Key::new("root", "integer"));
+        for (i, &k) in keys.iter().enumerate() {
+            registry.register(key(k), i as i32).unwrap();
+        }
+        registry.into()
+    };
+
+    let base = build(&["one", "two", "three"]);
+    assert!(base.diff(&build(&["one", "two", "three"])).is_empty());
+
+    let diff = base.diff(&build(&["one", "two", "three", "four"]));
+    assert_eq!(diff.added, ["four"]);
+    assert!(diff.removed.is_empty());
+    assert!(diff.remapped.is_empty());
+
+    let diff = base.diff(&build(&["two", "one"]));
+    assert!(diff.added.is_empty());
+    assert_eq!(diff.removed, ["three"]);
+    assert_eq!(diff.remapped, [("one", 0, 1), ("two", 1, 0)]);
+}
+
+#[test]
+fn get_or_register() {
+    let key = |value| Key::new("integer", value);
+    let mut registry: RegistryMut<&'static str, i32> =
+        RegistryMut::new(Key::new("root", "integer"));
+    registry.register_default(key("zero"), 0).unwrap();
+
+    let mut calls = 0;
+    let mut value_fn = || {
+        calls += 1;
+        1
+    };
+    let first = registry.get_or_register(key("one"), &mut value_fn);
+    let second = registry.get_or_register(key("one"), &mut value_fn);
+    assert_eq!(first, 1);
+    assert_eq!(first, second);
+    assert_eq!(calls, 1);
+    assert_eq!(registry.default_key(), Some(&key("zero")));
+}
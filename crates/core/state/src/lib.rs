@@ -70,17 +70,8 @@ impl<T> State<'_, T> {
         ) else {
             return Ok(self);
         };
-        if next == index {
-            Ok(self)
-        } else {
-            self.table
-                .get()
-                .expect("state not initialized")
-                .get(prop.name())
-                .ok_or_else(|| Error::PropertyNotFound(prop.name().to_owned()))
-                .and_then(|map| map.get(&next).ok_or(Error::ValueNotFound(index)))
-                .map(|ptr| unsafe { ptr.as_ref() })
-        }
+        self.transition(prop, next)
+            .ok_or(Error::ValueNotFound(index))
     }
 
     /// Gets the state of this state with given property `prop` set to `value`.
@@ -103,17 +94,66 @@ impl<T> State<'_, T> {
             .get(prop.name())
             .ok_or_else(|| Error::PropertyNotFound(prop.name().to_owned()))?;
         let value = prop.wrap.index_of(&value).ok_or(Error::InvalidValue)?;
-        if value == index {
-            Ok(self)
-        } else {
-            self.table
-                .get()
-                .expect("state not initialized")
-                .get(prop.name())
-                .ok_or_else(|| Error::PropertyNotFound(prop.name().to_owned()))
-                .and_then(|map| map.get(&value).ok_or(Error::ValueNotFound(index)))
-                .map(|ptr| unsafe { ptr.as_ref() })
+        self.transition(prop, value)
+            .ok_or(Error::ValueNotFound(index))
+    }
+
+    /// Gets the neighbor state of this state with given property `prop` set to
+    /// the value at index `to_index`, by looking up the transition table directly.
+    ///
+    /// Returns `None` if the property is not present in this state, or no
+    /// state has the given value index.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the target state was dropped.
+    /// - Panics if this state is not fully initialized.
+    pub fn transition<W>(&self, prop: &Property<'_, W>, to_index: isize) -> Option<&Self> {
+        self.transition_by_name(prop.name(), to_index)
+    }
+
+    /// Gets the state of this state with every property named in `assignments`
+    /// set to the value at the paired index.
+    ///
+    /// Each assignment follows the transition table of the state reached by the
+    /// previous assignments, so later assignments override earlier ones on the
+    /// same property.
+    ///
+    /// # Errors
+    ///
+    /// - Errors if a property is not present in this state.
+    /// - Errors if no state has a value index.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if a target state was dropped.
+    /// - Panics if a reached state is not fully initialized.
+    pub fn with_all<'n, I>(&self, assignments: I) -> Result<&Self, Error>
+    where
+        I: IntoIterator<Item = (&'n str, isize)>,
+    {
+        assignments
+            .into_iter()
+            .try_fold(self, |state, (name, index)| {
+                if !state.entries.contains_key(name) {
+                    return Err(Error::PropertyNotFound(name.to_owned()));
+                }
+                state
+                    .transition_by_name(name, index)
+                    .ok_or(Error::ValueNotFound(index))
+            })
+    }
+
+    fn transition_by_name(&self, name: &str, to_index: isize) -> Option<&Self> {
+        if self.entries.get(name) == Some(&to_index) {
+            return Some(self);
         }
+        self.table
+            .get()
+            .expect("state not initialized")
+            .get(name)
+            .and_then(|map| map.get(&to_index))
+            .map(|ptr| unsafe { ptr.as_ref() })
     }
 
     /// Whether this state contains given property.
@@ -129,6 +169,37 @@ impl<T> State<'_, T> {
     }
 }
 
+impl<'a, T> State<'a, T> {
+    /// Gets the names of properties whose values differ between this state and
+    /// `other`, along with the value indices in this state and in `other`.
+    ///
+    /// Properties are yielded by name rather than as property objects, since
+    /// the type-erased form of a property is internal to this crate. Names are
+    /// unique within a [`States`], so they can be matched against
+    /// [`Property::name`] or passed to [`Self::with_all`].
+    ///
+    /// Both states should come from the same [`States`]. Comparing states with
+    /// different property sets is a logic error, which is only checked in debug
+    /// builds.
+    pub fn diff<'s>(
+        &'s self,
+        other: &'s Self,
+    ) -> impl Iterator<Item = (&'a str, isize, isize)> + 's {
+        debug_assert!(
+            self.entries.len() == other.entries.len()
+                && self
+                    .entries
+                    .keys()
+                    .all(|prop| other.entries.contains_key(prop)),
+            "states have different property sets"
+        );
+        self.entries.iter().filter_map(|(prop, &index)| {
+            let other_index = *other.entries.get(prop)?;
+            (index != other_index).then_some((prop.name, index, other_index))
+        })
+    }
+}
+
 fn obtain_next(value: isize, mut iter: impl Iterator<Item = isize>) -> Option<isize> {
     let mut first = None;
     while let Some(next) = iter.next() {
@@ -158,7 +229,6 @@ impl<T: Debug> Debug for State<'_, T> {
 #[doc(alias = "StateManager")]
 pub struct States<'a, T> {
     states: Vec<NonNull<State<'a, T>>>,
-    #[allow(unused)]
     props: BTreeMap<&'a str, ErasedProperty<'a>>,
 }
 
@@ -240,6 +310,75 @@ impl<'a, T> States<'a, T> {
         &self.states
     }
 
+    /// Gets all states with the given property `prop` set to `value`.
+    ///
+    /// The iterator is empty if the property is not present in the states, or
+    /// the value is not present in the property.
+    pub fn filter_by<V, W>(
+        &self,
+        prop: &Property<'_, W>,
+        value: V,
+    ) -> impl Iterator<Item = &State<'a, T>> + '_
+    where
+        W: BiIndex<V>,
+    {
+        let target = self.props.get(prop.name()).zip(prop.wrap.index_of(&value));
+        self.states
+            .iter()
+            .map(|state| unsafe { state.as_ref() })
+            .filter(move |state| {
+                target.is_some_and(|(prop, index)| state.entries.get(prop) == Some(&index))
+            })
+    }
+
+    /// Gets the state with exactly the given property names and value indices.
+    ///
+    /// Returns `None` if the assignments are partial, name a property more
+    /// than once, name a property not present in the states, or match no
+    /// state.
+    pub fn state_for<'n, I>(&self, assignments: I) -> Option<&State<'a, T>>
+    where
+        I: IntoIterator<Item = (&'n str, isize)>,
+    {
+        let mut map = AHashMap::new();
+        for (name, index) in assignments {
+            if map.insert(name, index).is_some() {
+                return None;
+            }
+        }
+        self.states
+            .iter()
+            .map(|state| unsafe { state.as_ref() })
+            .find(|state| {
+                state.entries.len() == map.len()
+                    && state
+                        .entries
+                        .iter()
+                        .all(|(prop, index)| map.get(prop.name) == Some(index))
+            })
+    }
+
+    /// Dumps every state as a map from property names to value names, in
+    /// the order of [`Self::states`].
+    ///
+    /// This is intended for debugging. Values without a name are left out.
+    pub fn to_debug_vec(&self) -> Vec<BTreeMap<&'a str, String>> {
+        self.states
+            .iter()
+            .map(|state| {
+                unsafe { state.as_ref() }
+                    .entries
+                    .iter()
+                    .filter_map(|(prop, &index)| {
+                        prop.wrap
+                            .erased_to_name(index)
+                            .map(|name| (prop.name, name.into_owned()))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
     /// Gets the default state.
     ///
     /// # Panics
@@ -296,6 +435,8 @@ impl<'a, T> StatesMut<'a, T> {
     /// - Errors if the property contains <= 1 possible values.
     /// - Errors if the states contains duplicated properties.
     /// - Errors if any of the value name is invalid.
+    /// - Errors if the values are not mapped to distinct reversible indices.
+    ///   See [`Property::validate`].
     #[allow(clippy::missing_panics_doc)]
     pub fn add<'p, W, G>(&mut self, prop: &'a Property<'p, W>) -> Result<(), Error>
     where
@@ -307,6 +448,7 @@ impl<'a, T> StatesMut<'a, T> {
         if !reg.is_match(prop.name()) {
             return Err(Error::InvalidPropertyName(prop.name().to_owned()));
         }
+        prop.validate()?;
         let mut len = 0;
         for val in prop.wrap.erased_iter_typed() {
             len += 1;
@@ -328,6 +470,12 @@ impl<'a, T> StatesMut<'a, T> {
         self.props.push(prop.into());
         Ok(())
     }
+
+    /// Removes all added properties, so this instance could be reused.
+    #[inline]
+    pub fn clear_props(&mut self) {
+        self.props.clear();
+    }
 }
 
 impl<'a, T> StatesMut<'a, T>
@@ -339,6 +487,15 @@ where
     pub fn freeze(self) -> States<'a, T> {
         States::new(self.props, self.data)
     }
+
+    /// Freezes the state without consuming this instance.
+    ///
+    /// This clones the added properties and the data, so this instance
+    /// could be frozen repeatedly.
+    #[inline]
+    pub fn freeze_ref(&self) -> States<'a, T> {
+        States::new(self.props.iter().cloned(), self.data.clone())
+    }
 }
 
 impl<'a, T> From<StatesMut<'a, T>> for States<'a, T>
@@ -377,6 +534,8 @@ pub enum Error {
     },
     /// The states contains duplicated properties.
     DuplicatedProperty(String),
+    /// The values of the property are not mapped to distinct reversible indices.
+    MalformedIndex(String),
 }
 
 impl Display for Error {
@@ -394,6 +553,7 @@ impl Display for Error {
                 write!(f, "invalid value name: {value} for property {property}")
             }
             Error::DuplicatedProperty(prop) => write!(f, "duplicated property: {}", prop),
+            Error::MalformedIndex(prop) => write!(f, "malformed value indices of property {prop}"),
         }
     }
 }
@@ -3,6 +3,7 @@
 use std::{
     any::TypeId,
     borrow::{Borrow, Cow},
+    collections::HashSet,
     fmt::Debug,
     hash::Hash,
     marker::PhantomData,
@@ -79,6 +80,36 @@ impl<'a, W> Property<'a, W> {
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    /// Validates that every value of this property maps to a distinct index,
+    /// and that every index maps back to the same value.
+    ///
+    /// The indices are not required to be contiguous.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the indices of the values are missing, duplicated or not reversible.
+    pub fn validate<G>(&self) -> Result<(), crate::Error>
+    where
+        W: BiIndex<G>,
+        for<'w> &'w W: IntoIterator<Item = G>,
+    {
+        let mut indices = HashSet::new();
+        for value in &self.wrap {
+            let valid = self.wrap.index_of(&value).is_some_and(|index| {
+                indices.insert(index)
+                    && self
+                        .wrap
+                        .index(index)
+                        .and_then(|value| self.wrap.index_of(&value))
+                        == Some(index)
+            });
+            if !valid {
+                return Err(crate::Error::MalformedIndex(self.name().to_owned()));
+            }
+        }
+        Ok(())
+    }
 }
 
 impl<'a, 'p, W> From<&'a Property<'p, W>> for ErasedProperty<'a>
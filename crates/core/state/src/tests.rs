@@ -37,3 +37,194 @@ fn with_cycle() {
     let state = state.cycle(&BOOL_PROPERTY).unwrap();
     assert_eq!(state.get(&BOOL_PROPERTY), Some(false));
 }
+
+#[test]
+fn freeze_ref() {
+    let mut states = StatesMut::new(());
+    states.add(&INT_PROPERTY).unwrap();
+    states.add(&BOOL_PROPERTY).unwrap();
+
+    assert_eq!(states.freeze_ref().len(), 6);
+    assert_eq!(states.freeze_ref().len(), 6);
+
+    states.clear_props();
+    states.add(&BOOL_PROPERTY).unwrap();
+    assert_eq!(states.freeze().len(), 2);
+}
+
+#[test]
+fn validate() {
+    use crate::property::{BiIndex, Property};
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Collapsed;
+
+    impl BiIndex<i32> for Collapsed {
+        fn index(&self, _index: isize) -> Option<i32> {
+            Some(0)
+        }
+
+        fn index_of(&self, _value: &i32) -> Option<isize> {
+            Some(0)
+        }
+    }
+
+    impl IntoIterator for &Collapsed {
+        type Item = i32;
+        type IntoIter = std::ops::Range<i32>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            0..2
+        }
+    }
+
+    assert!(INT_PROPERTY.validate().is_ok());
+    assert!(BOOL_PROPERTY.validate().is_ok());
+    assert!(Property::new("collapsed", Collapsed).validate().is_err());
+}
+
+#[test]
+fn transition() {
+    let mut states = StatesMut::new(());
+    states.add(&INT_PROPERTY).unwrap();
+    states.add(&BOOL_PROPERTY).unwrap();
+    let states = states.freeze();
+
+    let state = states.default_state();
+    assert!(std::ptr::eq(
+        state.transition(&INT_PROPERTY, 1).unwrap(),
+        state
+    ));
+    let next = state.transition(&INT_PROPERTY, 3).unwrap();
+    assert_eq!(next.get(&INT_PROPERTY), Some(3));
+    assert_eq!(next.get(&BOOL_PROPERTY), Some(false));
+    assert!(state.transition(&INT_PROPERTY, 4).is_none());
+}
+
+#[test]
+fn filter_by() {
+    static OTHER_BOOL_PROPERTY: BoolProperty<'static> =
+        BoolProperty::new("other_bool_property", BoolData);
+
+    let mut states = StatesMut::new(());
+    states.add(&BOOL_PROPERTY).unwrap();
+    states.add(&OTHER_BOOL_PROPERTY).unwrap();
+    let states = states.freeze();
+
+    let matched: Vec<_> = states.filter_by(&BOOL_PROPERTY, true).collect();
+    assert_eq!(matched.len(), 2);
+    assert!(matched
+        .iter()
+        .all(|state| state.get(&BOOL_PROPERTY) == Some(true)));
+
+    assert_eq!(states.filter_by(&INT_PROPERTY, 2).count(), 0);
+}
+
+#[test]
+fn diff() {
+    use crate::property::BiIndex as _;
+
+    let mut states = StatesMut::new(());
+    states.add(&INT_PROPERTY).unwrap();
+    states.add(&BOOL_PROPERTY).unwrap();
+    let states = states.freeze();
+
+    let state = states.default_state();
+    assert_eq!(state.diff(state).count(), 0);
+
+    let toggled = state.with(&BOOL_PROPERTY, true).unwrap();
+    let diff: Vec<_> = state.diff(toggled).collect();
+    assert_eq!(diff.len(), 1);
+    let (prop, from, to) = diff[0];
+    assert_eq!(prop, BOOL_PROPERTY.name());
+    assert_eq!(BOOL_PROPERTY.wrap.index(from), Some(false));
+    assert_eq!(BOOL_PROPERTY.wrap.index(to), Some(true));
+}
+
+#[test]
+fn with_all() {
+    let mut states = StatesMut::new(());
+    states.add(&INT_PROPERTY).unwrap();
+    states.add(&BOOL_PROPERTY).unwrap();
+    let states = states.freeze();
+
+    let state = states.default_state();
+    let chained = state
+        .with(&INT_PROPERTY, 3)
+        .unwrap()
+        .with(&BOOL_PROPERTY, true)
+        .unwrap();
+    let at_once = state
+        .with_all([(INT_PROPERTY.name(), 3), (BOOL_PROPERTY.name(), 1)])
+        .unwrap();
+    assert!(std::ptr::eq(chained, at_once));
+
+    assert!(matches!(
+        state.with_all([("missing", 0)]),
+        Err(crate::Error::PropertyNotFound(_))
+    ));
+    assert!(matches!(
+        state.with_all([(INT_PROPERTY.name(), 4)]),
+        Err(crate::Error::ValueNotFound(4))
+    ));
+}
+
+#[test]
+fn to_debug_vec() {
+    static OTHER_BOOL_PROPERTY: BoolProperty<'static> =
+        BoolProperty::new("other_bool_property", BoolData);
+
+    let mut states = StatesMut::new(());
+    states.add(&BOOL_PROPERTY).unwrap();
+    states.add(&OTHER_BOOL_PROPERTY).unwrap();
+    let states = states.freeze();
+
+    let dump = states.to_debug_vec();
+    assert_eq!(dump.len(), 4);
+    for (name, value) in [
+        ("false", "false"),
+        ("false", "true"),
+        ("true", "false"),
+        ("true", "true"),
+    ] {
+        assert!(dump.iter().any(|map| {
+            map.len() == 2
+                && map[BOOL_PROPERTY.name()] == name
+                && map[OTHER_BOOL_PROPERTY.name()] == value
+        }));
+    }
+}
+
+#[test]
+fn state_for() {
+    let mut states = StatesMut::new(());
+    states.add(&INT_PROPERTY).unwrap();
+    states.add(&BOOL_PROPERTY).unwrap();
+    let states = states.freeze();
+
+    let expected = states
+        .default_state()
+        .with(&INT_PROPERTY, 2)
+        .unwrap()
+        .with(&BOOL_PROPERTY, true)
+        .unwrap();
+    let found = states
+        .state_for([(INT_PROPERTY.name(), 2), (BOOL_PROPERTY.name(), 1)])
+        .unwrap();
+    assert!(std::ptr::eq(expected, found));
+
+    assert!(states
+        .state_for([(INT_PROPERTY.name(), 4), (BOOL_PROPERTY.name(), 1)])
+        .is_none());
+    assert!(states.state_for([(INT_PROPERTY.name(), 2)]).is_none());
+    assert!(states
+        .state_for([
+            (INT_PROPERTY.name(), 2),
+            (BOOL_PROPERTY.name(), 1),
+            ("missing", 0)
+        ])
+        .is_none());
+    assert!(states
+        .state_for([(INT_PROPERTY.name(), 2), (INT_PROPERTY.name(), 2)])
+        .is_none());
+}
@@ -0,0 +1,65 @@
+//! Builder for [`RawText`].
+
+use crate::{style::Style, RawText};
+
+/// Builder for creating a new [`RawText`].
+///
+/// See [`RawText::builder`].
+#[derive(Debug, Clone)]
+pub struct TextBuilder<T, StyleExt> {
+    content: Option<T>,
+    style: Style<StyleExt>,
+    sibs: Vec<RawText<T, StyleExt>>,
+}
+
+impl<T, StyleExt> TextBuilder<T, StyleExt> {
+    /// Applies the given content.
+    #[inline]
+    pub fn content(self, content: T) -> Self {
+        Self {
+            content: Some(content),
+            ..self
+        }
+    }
+
+    /// Applies the given style.
+    #[inline]
+    pub fn style(self, style: Style<StyleExt>) -> Self {
+        Self { style, ..self }
+    }
+
+    /// Appends the given text as a sibling.
+    #[inline]
+    pub fn append(mut self, text: RawText<T, StyleExt>) -> Self {
+        self.sibs.push(text);
+        self
+    }
+
+    /// Builds a new [`RawText`] with the given content, style and siblings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the content is not set.
+    pub fn build(self) -> RawText<T, StyleExt> {
+        RawText::with_sibs(
+            self.content.expect("content is required"),
+            self.style,
+            self.sibs,
+        )
+    }
+}
+
+impl<T, StyleExt> RawText<T, StyleExt>
+where
+    StyleExt: Default,
+{
+    /// Creates a new [`TextBuilder`] with the default style and no siblings.
+    #[inline]
+    pub fn builder() -> TextBuilder<T, StyleExt> {
+        TextBuilder {
+            content: None,
+            style: Style::default(),
+            sibs: Vec::new(),
+        }
+    }
+}
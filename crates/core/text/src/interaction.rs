@@ -0,0 +1,164 @@
+//! Interaction events of text styles.
+
+use std::ops::{Add, AddAssign};
+
+use crate::{style::Style, RawText};
+
+/// Style extension carrying click and hover events of a text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase"),
+    serde(bound(
+        serialize = "T: serde::Serialize",
+        deserialize = "T: serde::Deserialize<'de> + crate::Plain"
+    ))
+)]
+pub struct StyleInteraction<T> {
+    /// Event triggered when the text is clicked.
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none"),
+        serde(default)
+    )]
+    pub click_event: Option<ClickEvent>,
+
+    /// Event triggered when the text is hovered.
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none"),
+        serde(default)
+    )]
+    pub hover_event: Option<HoverEvent<T>>,
+}
+
+impl<T> Default for StyleInteraction<T> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            click_event: None,
+            hover_event: None,
+        }
+    }
+}
+
+impl<T> Add for StyleInteraction<T> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            click_event: rhs.click_event.or(self.click_event),
+            hover_event: rhs.hover_event.or(self.hover_event),
+        }
+    }
+}
+
+impl<T> AddAssign for StyleInteraction<T> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        if rhs.click_event.is_some() {
+            self.click_event = rhs.click_event;
+        }
+        if rhs.hover_event.is_some() {
+            self.hover_event = rhs.hover_event;
+        }
+    }
+}
+
+/// Event triggered when a text is clicked.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "action", content = "value", rename_all = "snake_case")
+)]
+#[non_exhaustive]
+pub enum ClickEvent {
+    /// Opens the given URL.
+    OpenUrl(String),
+    /// Runs the given command.
+    RunCommand(String),
+    /// Suggests the given command in the chat box.
+    SuggestCommand(String),
+    /// Copies the given string to the clipboard.
+    CopyToClipboard(String),
+}
+
+/// Event triggered when a text is hovered.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "action", content = "contents", rename_all = "snake_case"),
+    serde(bound(
+        serialize = "T: serde::Serialize",
+        deserialize = "T: serde::Deserialize<'de> + crate::Plain"
+    ))
+)]
+#[non_exhaustive]
+pub enum HoverEvent<T> {
+    /// Shows the given text.
+    ShowText(Box<RawText<T, StyleInteraction<T>>>),
+    /// Shows the tooltip of an item.
+    ShowItem(HoverItem),
+    /// Shows the information of an entity.
+    ShowEntity(HoverEntity<T>),
+}
+
+/// Item shown by [`HoverEvent::ShowItem`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HoverItem {
+    /// Identifier of the item.
+    pub id: String,
+    /// Count of the item stack.
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none"),
+        serde(default)
+    )]
+    pub count: Option<u32>,
+}
+
+/// Entity shown by [`HoverEvent::ShowEntity`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "T: serde::Serialize",
+        deserialize = "T: serde::Deserialize<'de> + crate::Plain"
+    ))
+)]
+pub struct HoverEntity<T> {
+    /// Identifier of the entity type.
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub ty: String,
+    /// UUID of the entity.
+    pub id: String,
+    /// Custom name of the entity.
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none"),
+        serde(default)
+    )]
+    pub name: Option<Box<RawText<T, StyleInteraction<T>>>>,
+}
+
+impl<T> Style<StyleInteraction<T>> {
+    /// Returns this style with the given click event.
+    #[inline]
+    pub fn with_click_event(mut self, event: ClickEvent) -> Self {
+        self.ext.click_event = Some(event);
+        self
+    }
+
+    /// Returns this style with the given hover event.
+    #[inline]
+    pub fn with_hover_event(mut self, event: HoverEvent<T>) -> Self {
+        self.ext.hover_event = Some(event);
+        self
+    }
+}
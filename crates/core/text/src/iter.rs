@@ -1,13 +1,19 @@
 //! Iterator types for text processing.
+//!
+//! Iterators walk the text tree with an explicit stack, so deeply nested
+//! texts do not overflow the call stack.
 
-use std::{fmt::Debug, ops::Add};
+use std::{fmt::Debug, ops::Add, slice};
 
-use crate::style::Style;
+use crate::{style::Style, RawText};
+
+/// Siblings left to visit, along with the accumulated style of their parent.
+type StyledFrame<'a, T, StyleExt> = (slice::Iter<'a, RawText<T, StyleExt>>, Style<StyleExt>);
 
 /// An iterator over the content and style of a text.
 pub struct StyledIter<'a, T, StyleExt> {
-    pub(crate) style: &'a Style<StyleExt>,
-    pub(crate) inner: Box<dyn Iterator<Item = (&'a T, Style<StyleExt>)> + 'a>,
+    pub(crate) root: Option<&'a RawText<T, StyleExt>>,
+    pub(crate) stack: Vec<StyledFrame<'a, T, StyleExt>>,
 }
 
 impl<'a, T, StyleExt> Iterator for StyledIter<'a, T, StyleExt>
@@ -16,45 +22,56 @@ where
 {
     type Item = (&'a T, Style<StyleExt>);
 
-    #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        let (content, style) = self.inner.next()?;
-        Some((content, self.style.clone() + style))
-    }
+        if let Some(root) = self.root.take() {
+            let style = root.style().clone();
+            self.stack.push((root.sibs().iter(), style.clone()));
+            return Some((root.content(), style));
+        }
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        self.inner.size_hint()
+        loop {
+            let (sibs, parent) = self.stack.last_mut()?;
+            if let Some(text) = sibs.next() {
+                let style = parent.clone() + text.style().clone();
+                self.stack.push((text.sibs().iter(), style.clone()));
+                return Some((text.content(), style));
+            }
+            self.stack.pop();
+        }
     }
 }
 
 impl<T, StyleExt> Debug for StyledIter<'_, T, StyleExt> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("StyledIter").finish()
+        f.debug_struct("StyledIter")
+            .field("depth", &self.stack.len())
+            .finish()
     }
 }
 
 /// An iterator over the content of a text.
-pub struct Iter<'a, T> {
-    pub(crate) inner: Box<dyn Iterator<Item = &'a T> + 'a>,
+pub struct Iter<'a, T, StyleExt> {
+    pub(crate) stack: Vec<slice::Iter<'a, RawText<T, StyleExt>>>,
 }
 
-impl<'a, T> Iterator for Iter<'a, T> {
+impl<'a, T, StyleExt> Iterator for Iter<'a, T, StyleExt> {
     type Item = &'a T;
 
-    #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.inner.next()
-    }
-
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        self.inner.size_hint()
+        loop {
+            if let Some(text) = self.stack.last_mut()?.next() {
+                self.stack.push(text.sibs().iter());
+                return Some(text.content());
+            }
+            self.stack.pop();
+        }
     }
 }
 
-impl<T> Debug for Iter<'_, T> {
+impl<T, StyleExt> Debug for Iter<'_, T, StyleExt> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Iter").finish()
+        f.debug_struct("Iter")
+            .field("depth", &self.stack.len())
+            .finish()
     }
 }
@@ -1,14 +1,26 @@
 //! Minecraft text API.
 
+mod builder;
 mod error;
 mod iter;
 pub mod style;
 
+#[cfg(feature = "interaction")]
+pub mod interaction;
+
 #[cfg(feature = "serde")]
 mod _serde;
 
-use std::{borrow::Cow, fmt::Display, ops::Add};
+use std::{
+    borrow::{Borrow, Cow},
+    cell::Cell,
+    collections::HashMap,
+    fmt::Display,
+    hash::{BuildHasher, Hash},
+    ops::{Add, AddAssign},
+};
 
+pub use builder::TextBuilder;
 pub use error::Error;
 pub use iter::{Iter, StyledIter};
 use rimecraft_global_cx::GlobalContext;
@@ -99,17 +111,39 @@ impl<T, StyleExt> RawText<T, StyleExt> {
         self.sibs.push(text);
     }
 
+    /// Concatenates the other text to this text, by appending it as a sibling.
+    ///
+    /// Styles of both texts are kept as is, but the other text inherits
+    /// style fields it does not set from this text, like any sibling does.
+    #[inline]
+    pub fn concat(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+
     /// Returns an iterator over the content of this text.
     #[inline]
-    pub fn iter(&self) -> Iter<'_, T> {
+    pub fn iter(&self) -> Iter<'_, T, StyleExt> {
         Iter {
-            inner: Box::new(
-                std::iter::once(&self.content).chain(self.sibs.iter().flat_map(Self::iter)),
-            ),
+            stack: vec![std::slice::from_ref(self).iter()],
         }
     }
 }
 
+impl<T, StyleExt> RawText<T, StyleExt>
+where
+    T: PartialEq,
+{
+    /// Whether the flattened content sequence of this text equals the other's,
+    /// regardless of how the siblings are structured.
+    ///
+    /// Styles are not compared.
+    #[inline]
+    pub fn content_eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
 impl<T, StyleExt> RawText<T, StyleExt>
 where
     StyleExt: Add<Output = StyleExt> + Clone,
@@ -118,18 +152,32 @@ where
     #[inline]
     pub fn styled_iter(&self) -> StyledIter<'_, T, StyleExt> {
         StyledIter {
-            style: &self.style,
-            inner: Box::new(
-                std::iter::once((self.content(), self.style().clone()))
-                    .chain(self.sibs.iter().flat_map(Self::styled_iter)),
-            ),
+            root: Some(self),
+            stack: Vec::new(),
         }
     }
 }
 
+impl<T, StyleExt> RawText<T, StyleExt>
+where
+    T: Display,
+    StyleExt: Add<Output = StyleExt> + Clone,
+{
+    /// Flattens this text into displayed strings, each paired with the
+    /// style it inherits from its parents.
+    ///
+    /// Contents displaying as empty strings are skipped.
+    pub fn flatten(&self) -> Vec<(String, Style<StyleExt>)> {
+        self.styled_iter()
+            .map(|(content, style)| (content.to_string(), style))
+            .filter(|(content, _)| !content.is_empty())
+            .collect()
+    }
+}
+
 impl<'a, T, StyleExt> IntoIterator for &'a RawText<T, StyleExt> {
     type Item = &'a T;
-    type IntoIter = Iter<'a, T>;
+    type IntoIter = Iter<'a, T, StyleExt>;
 
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
@@ -151,6 +199,35 @@ where
     }
 }
 
+impl<T, StyleExt> Add for RawText<T, StyleExt> {
+    type Output = Self;
+
+    /// See [`RawText::concat`].
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        self.concat(rhs)
+    }
+}
+
+impl<T, StyleExt> AddAssign for RawText<T, StyleExt> {
+    /// See [`RawText::concat`].
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.sibs.reserve(1);
+        self.sibs.push(rhs);
+    }
+}
+
+impl<T, StyleExt> Drop for RawText<T, StyleExt> {
+    fn drop(&mut self) {
+        // Drop siblings iteratively, as dropping deep trees recursively overflows the stack.
+        let mut stack = std::mem::take(&mut self.sibs);
+        while let Some(mut text) = stack.pop() {
+            stack.append(&mut text.sibs);
+        }
+    }
+}
+
 impl<T, StyleExt> Display for RawText<T, StyleExt>
 where
     T: Display,
@@ -164,6 +241,86 @@ where
     }
 }
 
+impl<T, StyleExt> RawText<T, StyleExt>
+where
+    StyleExt: Clone,
+{
+    /// Rebuilds this text with every content mapped by `f`, keeping styles and
+    /// the tree structure.
+    ///
+    /// Siblings for which `visit` returns `false` are left out, along with
+    /// their own siblings. The tree is walked with an explicit stack, so deep
+    /// texts do not overflow.
+    fn map_tree<F, V>(&self, mut f: F, mut visit: V) -> Self
+    where
+        F: FnMut(&Self) -> T,
+        V: FnMut(&Self) -> bool,
+    {
+        let mut map = |text: &Self| Self::new(f(text), text.style.clone());
+        let mut stack = vec![(map(self), self.sibs.iter())];
+        while let Some((_, sibs)) = stack.last_mut() {
+            if let Some(text) = sibs.next().filter(|text| visit(text)) {
+                stack.push((map(text), text.sibs.iter()));
+            } else if let Some((text, _)) = stack.pop() {
+                match stack.last_mut() {
+                    Some((parent, _)) => parent.push(text),
+                    None => return text,
+                }
+            }
+        }
+        unreachable!("the root text is always popped last")
+    }
+}
+
+impl<T, StyleExt> RawText<T, StyleExt>
+where
+    T: Display + Plain,
+    StyleExt: Clone,
+{
+    /// Truncates this text to at most `max_chars` displayed characters,
+    /// keeping the tree structure and styles of the remaining prefix.
+    ///
+    /// Every content is converted to a literal, and the content crossing the
+    /// boundary is split. Characters are counted by [`char`], not by byte.
+    pub fn truncate(&self, max_chars: usize) -> Self {
+        let budget = Cell::new(max_chars);
+        self.map_tree(
+            |text| {
+                let content: String = text
+                    .content
+                    .to_string()
+                    .chars()
+                    .take(budget.get())
+                    .collect();
+                budget.set(budget.get() - content.chars().count());
+                T::from_literal(&content)
+            },
+            |_| budget.get() > 0,
+        )
+    }
+}
+
+impl<T, StyleExt> RawText<T, StyleExt>
+where
+    T: Translatable + Clone,
+    StyleExt: Clone,
+{
+    /// Resolves every translatable content of this text into its plain form
+    /// using the given resolver, keeping styles and siblings as is.
+    pub fn resolved<R>(&self, resolver: &R) -> Self
+    where
+        R: ResolveLocalization<T> + ?Sized,
+    {
+        self.map_tree(
+            |text| match text.content.translation() {
+                Some((key, args)) => resolver.resolve(key, &args),
+                None => text.content.clone(),
+            },
+            |_| true,
+        )
+    }
+}
+
 /// Text content that supports literal text conversion.
 pub trait Plain: Sized {
     /// Converts a string literal to the text content.
@@ -200,6 +357,69 @@ pub trait Localize {
     fn localization_key(&self) -> Cow<'_, str>;
 }
 
+/// Text content that may be translatable.
+pub trait Translatable {
+    /// Returns the translation key and arguments of this content,
+    /// or `None` if this content is not translatable.
+    fn translation(&self) -> Option<(&str, Vec<&str>)>;
+}
+
+/// A resolver of translation keys into text content.
+pub trait ResolveLocalization<T> {
+    /// Resolves the given translation key with the given arguments.
+    fn resolve(&self, key: &str, args: &[&str]) -> T;
+}
+
+/// Resolves keys against a language map, falling back to the key itself
+/// if it is missing.
+///
+/// Arguments are substituted for `%s` in order, or for `%1$s` by position,
+/// and `%%` is an escaped `%`.
+impl<T, K, V, S> ResolveLocalization<T> for HashMap<K, V, S>
+where
+    T: Plain,
+    K: Borrow<str> + Hash + Eq,
+    V: AsRef<str>,
+    S: BuildHasher,
+{
+    fn resolve(&self, key: &str, args: &[&str]) -> T {
+        let Some(pattern) = self.get(key) else {
+            return T::from_literal(key);
+        };
+        let mut pattern = pattern.as_ref();
+        let mut resolved = String::with_capacity(pattern.len());
+        let mut next_arg = 0;
+        while let Some(i) = pattern.find('%') {
+            resolved.push_str(&pattern[..i]);
+            let spec = &pattern[i + 1..];
+            let digits = spec.len() - spec.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            if let Some(rest) = spec.strip_prefix('%') {
+                resolved.push('%');
+                pattern = rest;
+            } else if let Some(rest) = spec.strip_prefix('s') {
+                resolved.push_str(args.get(next_arg).copied().unwrap_or_default());
+                next_arg += 1;
+                pattern = rest;
+            } else if let Some(rest) = spec[digits..].strip_prefix("$s").filter(|_| digits > 0) {
+                let index = spec[..digits].parse::<usize>().unwrap_or(0);
+                resolved.push_str(
+                    index
+                        .checked_sub(1)
+                        .and_then(|i| args.get(i))
+                        .copied()
+                        .unwrap_or_default(),
+                );
+                pattern = rest;
+            } else {
+                resolved.push('%');
+                pattern = spec;
+            }
+        }
+        resolved.push_str(pattern);
+        T::from_literal(&resolved)
+    }
+}
+
 /// A seed for encoding and decoding [`Text`] through `edcode2` crate.
 #[cfg(feature = "edcode")]
 pub type EdcodeSeed<Cx> = rimecraft_global_cx::edcode::Nbt<Text<Cx>, Cx>;
@@ -35,3 +35,320 @@ fn display() {
         "Hello, world! Genshin Impact, a game by miHoYo, boot! opssw"
     );
 }
+
+#[test]
+fn content_eq() {
+    let mut nested: RawText<&str, ()> = "a".into();
+    let mut sib: RawText<_, ()> = "b".into();
+    sib.push("c".into());
+    nested.push(sib);
+
+    let mut flat: RawText<&str, ()> = "a".into();
+    flat.push("b".into());
+    flat.push("c".into());
+
+    assert_ne!(nested, flat);
+    assert!(nested.content_eq(&flat));
+
+    flat.push("d".into());
+    assert!(!nested.content_eq(&flat));
+}
+
+#[test]
+#[cfg(feature = "interaction")]
+fn interaction_style() {
+    use crate::interaction::{ClickEvent, HoverEvent, StyleInteraction};
+
+    let hover: RawText<&str, StyleInteraction<&str>> = "hint".into();
+    let style = Style::<StyleInteraction<&str>>::default()
+        .with_click_event(ClickEvent::RunCommand("/help".to_owned()))
+        .with_hover_event(HoverEvent::ShowText(Box::new(hover)));
+
+    let merged = style.clone()
+        + Style::default().with_click_event(ClickEvent::OpenUrl("https://example.com".to_owned()));
+    assert_eq!(
+        merged.ext.click_event,
+        Some(ClickEvent::OpenUrl("https://example.com".to_owned()))
+    );
+    assert_eq!(merged.ext.hover_event, style.ext.hover_event);
+}
+
+#[test]
+#[cfg(all(feature = "interaction", feature = "serde"))]
+fn interaction_serde() {
+    use serde::Serialize;
+    use serde_json::json;
+
+    use crate::interaction::{ClickEvent, HoverEntity, HoverEvent, HoverItem, StyleInteraction};
+
+    #[derive(Debug, Clone, Serialize)]
+    struct Literal {
+        text: String,
+    }
+
+    impl From<&str> for Literal {
+        fn from(value: &str) -> Self {
+            Self {
+                text: value.to_owned(),
+            }
+        }
+    }
+
+    let hint: RawText<Literal, StyleInteraction<Literal>> = Literal::from("hint").into();
+    let hint_json = serde_json::to_value(&hint).unwrap();
+    assert_eq!(hint_json["text"], "hint");
+
+    let cases = [
+        (
+            StyleInteraction {
+                click_event: Some(ClickEvent::OpenUrl("https://example.com".to_owned())),
+                hover_event: None,
+            },
+            json!({ "clickEvent": { "action": "open_url", "value": "https://example.com" } }),
+        ),
+        (
+            StyleInteraction {
+                click_event: Some(ClickEvent::RunCommand("/help".to_owned())),
+                hover_event: None,
+            },
+            json!({ "clickEvent": { "action": "run_command", "value": "/help" } }),
+        ),
+        (
+            StyleInteraction {
+                click_event: Some(ClickEvent::SuggestCommand("/tp".to_owned())),
+                hover_event: None,
+            },
+            json!({ "clickEvent": { "action": "suggest_command", "value": "/tp" } }),
+        ),
+        (
+            StyleInteraction {
+                click_event: Some(ClickEvent::CopyToClipboard("copied".to_owned())),
+                hover_event: None,
+            },
+            json!({ "clickEvent": { "action": "copy_to_clipboard", "value": "copied" } }),
+        ),
+        (
+            StyleInteraction {
+                click_event: None,
+                hover_event: Some(HoverEvent::ShowText(Box::new(hint.clone()))),
+            },
+            json!({ "hoverEvent": { "action": "show_text", "contents": hint_json } }),
+        ),
+        (
+            StyleInteraction {
+                click_event: None,
+                hover_event: Some(HoverEvent::ShowItem(HoverItem {
+                    id: "minecraft:stone".to_owned(),
+                    count: Some(2),
+                })),
+            },
+            json!({
+                "hoverEvent": {
+                    "action": "show_item",
+                    "contents": { "id": "minecraft:stone", "count": 2 }
+                }
+            }),
+        ),
+        (
+            StyleInteraction {
+                click_event: None,
+                hover_event: Some(HoverEvent::ShowEntity(HoverEntity {
+                    ty: "minecraft:pig".to_owned(),
+                    id: "00000000-0000-0000-0000-000000000000".to_owned(),
+                    name: Some(Box::new(hint.clone())),
+                })),
+            },
+            json!({
+                "hoverEvent": {
+                    "action": "show_entity",
+                    "contents": {
+                        "type": "minecraft:pig",
+                        "id": "00000000-0000-0000-0000-000000000000",
+                        "name": hint_json
+                    }
+                }
+            }),
+        ),
+    ];
+    for (interaction, expected) in cases {
+        assert_eq!(serde_json::to_value(&interaction).unwrap(), expected);
+    }
+}
+
+#[test]
+fn display_deep() {
+    const DEPTH: usize = 100_000;
+
+    let mut text: RawText<_, ()> = Content::from("a").into();
+    for _ in 0..DEPTH {
+        text = RawText::with_sibs(Content::from("a"), Style::default(), vec![text]);
+    }
+    assert_eq!(text.to_string().len(), DEPTH + 1);
+    assert_eq!(text.iter().count(), DEPTH + 1);
+}
+
+#[test]
+fn concat() {
+    let text: RawText<&str, ()> = RawText::from("a").concat("b".into());
+    let mut text = text + "c".into();
+    text += "d".into();
+
+    assert_eq!(text.sibs().len(), 3);
+    assert_eq!(text.iter().copied().collect::<String>(), "abcd");
+}
+
+#[test]
+fn builder() {
+    let style = Style::<()> {
+        bold: Some(true),
+        ..Default::default()
+    };
+    let text = RawText::builder()
+        .content("a")
+        .style(style)
+        .append("b".into())
+        .append("c".into())
+        .build();
+
+    assert_eq!(
+        text,
+        RawText::with_sibs("a", style, vec!["b".into(), "c".into()])
+    );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+struct Ext;
+
+impl Add for Ext {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, _rhs: Self) -> Self::Output {
+        self
+    }
+}
+
+#[test]
+fn flatten() {
+    let bold = Style::<Ext> {
+        bold: Some(true),
+        ..Default::default()
+    };
+    let italic = Style::<Ext> {
+        italic: Some(true),
+        ..Default::default()
+    };
+
+    let text = RawText::with_sibs(
+        "a",
+        bold,
+        vec![RawText::with_sibs("b", italic, vec!["c".into()]), "".into()],
+    );
+    let both = Style {
+        bold: Some(true),
+        italic: Some(true),
+        ..Default::default()
+    };
+    assert_eq!(
+        text.flatten(),
+        [
+            ("a".to_owned(), bold),
+            ("b".to_owned(), both),
+            ("c".to_owned(), both),
+        ]
+    );
+
+    assert_eq!(
+        RawText::new("a", italic).flatten(),
+        [("a".to_owned(), italic)]
+    );
+    assert!(RawText::<&str, Ext>::from("").flatten().is_empty());
+}
+
+#[test]
+fn truncate() {
+    let bold = Style::<()> {
+        bold: Some(true),
+        ..Default::default()
+    };
+    let text: RawText<String, ()> = RawText::with_sibs(
+        "ab".to_owned(),
+        Style::default(),
+        vec![
+            RawText::new("cdé".to_owned(), bold),
+            RawText::new("fg".to_owned(), bold),
+        ],
+    );
+
+    let truncated = text.truncate(4);
+    assert_eq!(
+        truncated,
+        RawText::with_sibs(
+            "ab".to_owned(),
+            Style::default(),
+            vec![RawText::new("cd".to_owned(), bold)],
+        )
+    );
+    assert_eq!(text.truncate(5).to_string(), "abcdé");
+    assert_eq!(text.truncate(0).to_string(), "");
+    assert_eq!(text.truncate(10), text);
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Translated {
+    Literal(String),
+    Translatable(String, Vec<String>),
+}
+
+impl From<&str> for Translated {
+    #[inline]
+    fn from(value: &str) -> Self {
+        Self::Literal(value.to_owned())
+    }
+}
+
+impl Translatable for Translated {
+    fn translation(&self) -> Option<(&str, Vec<&str>)> {
+        match self {
+            Self::Literal(_) => None,
+            Self::Translatable(key, args) => Some((key, args.iter().map(String::as_str).collect())),
+        }
+    }
+}
+
+#[test]
+fn resolved() {
+    let lang: HashMap<&str, &str> = [("chat.type.text", "<%s> %s"), ("swap", "%2$s %1$s %%")]
+        .into_iter()
+        .collect();
+    let bold = Style::<()> {
+        bold: Some(true),
+        ..Default::default()
+    };
+    let text: RawText<Translated, ()> = RawText::with_sibs(
+        Translated::Translatable(
+            "chat.type.text".to_owned(),
+            vec!["Steve".to_owned(), "hi".to_owned()],
+        ),
+        bold,
+        vec![
+            Translated::Translatable("swap".to_owned(), vec!["a".to_owned(), "b".to_owned()])
+                .into(),
+            Translated::Translatable("missing.key".to_owned(), vec![]).into(),
+            Translated::from("literal").into(),
+        ],
+    );
+
+    assert_eq!(
+        text.resolved(&lang),
+        RawText::with_sibs(
+            "<Steve> hi".into(),
+            bold,
+            vec![
+                Translated::from("b a %").into(),
+                Translated::from("missing.key").into(),
+                Translated::from("literal").into(),
+            ],
+        )
+    );
+}
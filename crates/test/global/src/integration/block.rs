@@ -0,0 +1,9 @@
+//! `rimecraft-block` integrations.
+
+#![cfg(feature = "block")]
+
+use crate::TestContext;
+
+impl block::ProvideBlockStateExtTy for TestContext {
+    type BlockStateExt = ();
+}
@@ -26,6 +26,7 @@ pub use ::registry as __priv_registry;
 
 /// Integration with several Rimecraft crates.
 pub mod integration {
+    pub mod block;
     pub mod registry;
 }
 
@@ -4,6 +4,7 @@
 
 use core::{
     any::TypeId,
+    mem::{align_of, size_of},
     ops::{Deref, DerefMut},
 };
 
@@ -129,3 +130,103 @@ where
 {
     type StaticRepr = Self;
 }
+
+/// Whether the layout of type `L` is compatible with type `R`, that is,
+/// both types have the same size and alignment.
+///
+/// This is stricter than the check [`core::mem::transmute`] performs, which
+/// only compares sizes, so it could be used to guard reinterpretation of
+/// references and slices in generic code at compile time.
+///
+/// # Examples
+///
+/// ```
+/// # use rimecraft_downcast::layout_compatible;
+/// const { assert!(layout_compatible::<u32, i32>()) };
+/// assert!(!layout_compatible::<u32, u64>());
+/// assert!(!layout_compatible::<[u8; 4], u32>());
+/// ```
+#[inline]
+pub const fn layout_compatible<L, R>() -> bool {
+    size_of::<L>() == size_of::<R>() && align_of::<L>() == align_of::<R>()
+}
+
+/// Casts a slice of type `L` into a slice of type `R` with the same length.
+///
+/// # Panics
+///
+/// Panics if the type `L` is not the same as `R` regardless of lifetimes,
+/// or their layouts are not compatible.
+///
+/// # Safety
+///
+/// This function could not make sure the lifetime is safe, as lifetimes
+/// are erased when comparing the types.
+#[inline]
+pub unsafe fn cast_slice<L, R>(slice: &[L]) -> &[R] {
+    assert_slice_cast::<L, R>();
+    unsafe { core::slice::from_raw_parts(slice.as_ptr().cast(), slice.len()) }
+}
+
+/// Casts a mutable slice of type `L` into a mutable slice of type `R` with
+/// the same length.
+///
+/// # Panics
+///
+/// Panics if the type `L` is not the same as `R` regardless of lifetimes,
+/// or their layouts are not compatible.
+///
+/// # Safety
+///
+/// This function could not make sure the lifetime is safe, as lifetimes
+/// are erased when comparing the types.
+#[inline]
+pub unsafe fn cast_slice_mut<L, R>(slice: &mut [L]) -> &mut [R] {
+    assert_slice_cast::<L, R>();
+    unsafe { core::slice::from_raw_parts_mut(slice.as_mut_ptr().cast(), slice.len()) }
+}
+
+/// Casts a reference of type `L` into a reference of type `R`, returning
+/// `None` if the types are not the same regardless of lifetimes.
+///
+/// # Safety
+///
+/// This function could not make sure the lifetime is safe, as lifetimes
+/// are erased when comparing the types.
+#[inline]
+pub unsafe fn try_cast_ref<L, R>(value: &L) -> Option<&R> {
+    castable::<L, R>().then(|| unsafe { &*core::ptr::from_ref(value).cast::<R>() })
+}
+
+/// Casts a mutable reference of type `L` into a mutable reference of type `R`,
+/// returning `None` if the types are not the same regardless of lifetimes.
+///
+/// # Safety
+///
+/// This function could not make sure the lifetime is safe, as lifetimes
+/// are erased when comparing the types.
+#[inline]
+pub unsafe fn try_cast_mut<L, R>(value: &mut L) -> Option<&mut R> {
+    castable::<L, R>().then(|| unsafe { &mut *core::ptr::from_mut(value).cast::<R>() })
+}
+
+#[inline]
+fn castable<L, R>() -> bool {
+    typeid::of::<L>() == typeid::of::<R>() && layout_compatible::<L, R>()
+}
+
+#[inline]
+fn assert_slice_cast<L, R>() {
+    assert_eq!(
+        typeid::of::<L>(),
+        typeid::of::<R>(),
+        "the element types should be the same"
+    );
+    assert!(
+        layout_compatible::<L, R>(),
+        "the element layouts should be compatible"
+    );
+}
+
+#[cfg(test)]
+mod tests;
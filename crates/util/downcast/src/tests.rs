@@ -0,0 +1,32 @@
+use crate::{cast_slice, cast_slice_mut, try_cast_mut, try_cast_ref};
+
+#[test]
+fn cast_slice_same() {
+    let values = [1u32, 2, 3];
+    let cast: &[u32] = unsafe { cast_slice(&values) };
+    assert_eq!(cast, &values);
+
+    let mut values = [1u32, 2, 3];
+    let cast: &mut [u32] = unsafe { cast_slice_mut(&mut values) };
+    cast[1] = 4;
+    assert_eq!(values, [1, 4, 3]);
+}
+
+#[test]
+#[should_panic = "the element types should be the same"]
+fn cast_slice_mismatch() {
+    let values = [1u32, 2, 3];
+    let _cast: &[i32] = unsafe { cast_slice(&values) };
+}
+
+#[test]
+fn try_cast() {
+    let value = 1u64;
+    assert_eq!(unsafe { try_cast_ref::<u64, u64>(&value) }, Some(&1));
+    assert_eq!(unsafe { try_cast_ref::<u64, u32>(&value) }, None);
+
+    let mut value = 1u64;
+    *unsafe { try_cast_mut::<u64, u64>(&mut value) }.unwrap() = 2;
+    assert_eq!(value, 2);
+    assert!(unsafe { try_cast_mut::<u64, i64>(&mut value) }.is_none());
+}
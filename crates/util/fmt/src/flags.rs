@@ -0,0 +1,149 @@
+//! Compact storage of formatting modifiers.
+
+use std::ops::{BitOr, BitOrAssign};
+
+use crate::Formatting;
+
+/// A bitset of modifier [`Formatting`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FormattingFlags(u8);
+
+impl FormattingFlags {
+    /// The empty set.
+    pub const EMPTY: Self = Self(0);
+    /// [`Formatting::Obfuscated`].
+    pub const OBFUSCATED: Self = Self(1 << 0);
+    /// [`Formatting::Bold`].
+    pub const BOLD: Self = Self(1 << 1);
+    /// [`Formatting::Strikethrough`].
+    pub const STRIKETHROUGH: Self = Self(1 << 2);
+    /// [`Formatting::Underline`].
+    pub const UNDERLINE: Self = Self(1 << 3);
+    /// [`Formatting::Italic`].
+    pub const ITALIC: Self = Self(1 << 4);
+
+    const MODIFIERS: [Formatting; 5] = [
+        Formatting::Obfuscated,
+        Formatting::Bold,
+        Formatting::Strikethrough,
+        Formatting::Underline,
+        Formatting::Italic,
+    ];
+
+    /// Creates flags from raw bits, discarding unknown bits.
+    #[inline]
+    pub const fn from_bits_truncate(bits: u8) -> Self {
+        Self(bits & 0b11111)
+    }
+
+    /// Returns the raw bits of the flags.
+    #[inline]
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Whether no modifiers are set.
+    #[inline]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Whether all modifiers in `other` are set.
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Sets the modifiers in `other`.
+    #[inline]
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0
+    }
+
+    /// Clears the modifiers in `other`.
+    #[inline]
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0
+    }
+
+    /// Returns an iterator over the modifiers in this set.
+    #[inline]
+    pub fn iter(self) -> FlagsIter {
+        FlagsIter {
+            flags: self,
+            index: 0,
+        }
+    }
+}
+
+impl From<Formatting> for FormattingFlags {
+    /// Converts a modifier into its flag.
+    ///
+    /// Non-modifier formattings convert into the empty set.
+    #[inline]
+    fn from(value: Formatting) -> Self {
+        match value {
+            Formatting::Obfuscated => Self::OBFUSCATED,
+            Formatting::Bold => Self::BOLD,
+            Formatting::Strikethrough => Self::STRIKETHROUGH,
+            Formatting::Underline => Self::UNDERLINE,
+            Formatting::Italic => Self::ITALIC,
+            _ => Self::EMPTY,
+        }
+    }
+}
+
+impl BitOr for FormattingFlags {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for FormattingFlags {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.insert(rhs)
+    }
+}
+
+impl FromIterator<Formatting> for FormattingFlags {
+    fn from_iter<T: IntoIterator<Item = Formatting>>(iter: T) -> Self {
+        iter.into_iter()
+            .fold(Self::EMPTY, |flags, fmt| flags | fmt.into())
+    }
+}
+
+impl IntoIterator for FormattingFlags {
+    type Item = Formatting;
+
+    type IntoIter = FlagsIter;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// The iterator returned by [`FormattingFlags::iter`].
+#[derive(Debug)]
+pub struct FlagsIter {
+    flags: FormattingFlags,
+    index: usize,
+}
+
+impl Iterator for FlagsIter {
+    type Item = Formatting;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(&fmt) = FormattingFlags::MODIFIERS.get(self.index) {
+            self.index += 1;
+            if self.flags.0 & (1 << (self.index - 1)) != 0 {
+                return Some(fmt);
+            }
+        }
+        None
+    }
+}
@@ -1,6 +1,9 @@
 //! Minecraft `Formatting` in Rust.
 
-use std::{fmt::Display, ops::Deref, sync::OnceLock};
+mod flags;
+mod span;
+
+use std::{borrow::Cow, fmt::Display, ops::Deref, sync::OnceLock};
 
 use rgb::RGB8;
 
@@ -9,6 +12,9 @@ use regex::Regex;
 #[cfg(not(feature = "regex"))]
 use regex_lite::Regex;
 
+pub use flags::{FlagsIter, FormattingFlags};
+pub use span::{FormattingSet, SetIter, SpanRanges};
+
 /// Color index of a formatting.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ColorIndex(pub Option<u32>);
@@ -34,6 +40,7 @@ impl TryFrom<i32> for ColorIndex {
 }
 
 static SANITIZE_REGEX: OnceLock<Regex> = OnceLock::new();
+static STRIP_REGEX: OnceLock<Regex> = OnceLock::new();
 
 macro_rules! formattings {
     ($($i:ident => $n:literal, $ln:literal, $sn:literal, $c:literal, $m:expr, $ci:literal, $cv:expr),*$(,)?) => {
@@ -254,6 +261,47 @@ impl Formatting {
         !self.is_modifier() && !matches!(self, Self::Reset)
     }
 
+    /// Returns the color formatting nearest to the given color in RGB space.
+    ///
+    /// Ties are broken by declaration order, so the first closest color wins.
+    pub fn closest_color(rgb: RGB8) -> Self {
+        let distance = |color: RGB8| {
+            [(rgb.r, color.r), (rgb.g, color.g), (rgb.b, color.b)]
+                .into_iter()
+                .map(|(a, b)| (a as i32 - b as i32).pow(2))
+                .sum::<i32>()
+        };
+        Self::VALUES
+            .iter()
+            .filter_map(|&fmt| fmt.color_value().map(|color| (fmt, distance(color))))
+            .min_by_key(|&(_, distance)| distance)
+            .map_or(Self::White, |(fmt, _)| fmt)
+    }
+
+    /// Removes all formatting codes from the given string.
+    ///
+    /// A code prefix not followed by a valid code is kept.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn strip_codes(input: &str) -> Cow<'_, str> {
+        STRIP_REGEX
+            .get_or_init(|| Regex::new("(?i)§[0-9a-fk-or]").unwrap())
+            .replace_all(input, "")
+    }
+
+    /// Splits the given string into runs of text, each along with the
+    /// formattings active for it.
+    ///
+    /// This follows the same rules as [`Self::span_ranges`]: modifiers
+    /// accumulate, colors clear the active modifiers, [`Self::Reset`] clears
+    /// all formattings, and invalid codes are dropped. Empty runs are skipped.
+    #[inline]
+    pub fn parse_segments(input: &str) -> Segments<'_> {
+        Segments {
+            input,
+            inner: Self::span_ranges(input),
+        }
+    }
+
     /// Get an iterator iterates over names of all formattings.
     #[inline]
     pub fn names() -> Names {
@@ -285,6 +333,24 @@ impl Iterator for Names {
     }
 }
 
+/// The iterator returned by [`Formatting::parse_segments`].
+#[derive(Debug)]
+pub struct Segments<'a> {
+    input: &'a str,
+    inner: SpanRanges<'a>,
+}
+
+impl<'a> Iterator for Segments<'a> {
+    type Item = (Vec<Formatting>, &'a str);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|(set, range)| (set.iter().collect(), &self.input[range]))
+    }
+}
+
 /// Item of [`Names`].
 #[derive(Debug)]
 pub struct Name {
@@ -328,5 +394,42 @@ impl Display for Formatting {
     }
 }
 
+/// Serde adapter representing a [`Formatting`] as its single-char code.
+///
+/// Use with `#[serde(with = "rimecraft_fmt::serde_code")]`.
+#[cfg(feature = "serde")]
+pub mod serde_code {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::Formatting;
+
+    /// Serializes the formatting as its code.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the serializer fails.
+    #[inline]
+    pub fn serialize<S>(value: &Formatting, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        value.code().serialize(serializer)
+    }
+
+    /// Deserializes a formatting from its code.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the input is not a valid formatting code.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Formatting, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        char::deserialize(deserializer)?
+            .try_into()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,150 @@
+//! Formatting runs of strings.
+
+use std::ops::Range;
+
+use crate::{FlagsIter, Formatting, FormattingFlags};
+
+/// The iterator returned by [`FormattingSet::iter`].
+pub type SetIter = std::iter::Chain<std::option::IntoIter<Formatting>, FlagsIter>;
+
+/// A set of active formattings, consisting of at most one color and any modifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FormattingSet {
+    color: Option<Formatting>,
+    modifiers: FormattingFlags,
+}
+
+impl FormattingSet {
+    /// Creates an empty formatting set.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            color: None,
+            modifiers: FormattingFlags::EMPTY,
+        }
+    }
+
+    /// Applies the given formatting to this set.
+    ///
+    /// Colors and [`Formatting::Reset`] clear all previous formattings,
+    /// while modifiers are added to the set.
+    pub fn apply(&mut self, formatting: Formatting) {
+        if formatting.is_modifier() {
+            self.modifiers.insert(formatting.into());
+        } else {
+            *self = Self {
+                color: formatting.is_color().then_some(formatting),
+                modifiers: FormattingFlags::EMPTY,
+            };
+        }
+    }
+
+    /// Returns the color of this set.
+    #[inline]
+    pub fn color(&self) -> Option<Formatting> {
+        self.color
+    }
+
+    /// Returns the modifiers of this set.
+    #[inline]
+    pub fn modifiers(&self) -> FormattingFlags {
+        self.modifiers
+    }
+
+    /// Whether this set contains the given formatting.
+    #[inline]
+    pub fn contains(&self, formatting: Formatting) -> bool {
+        self.color == Some(formatting)
+            || (formatting.is_modifier() && self.modifiers.contains(formatting.into()))
+    }
+
+    /// Returns an iterator over the formattings in this set, yielding the
+    /// color first and then the modifiers.
+    #[inline]
+    pub fn iter(self) -> SetIter {
+        self.color.into_iter().chain(self.modifiers.iter())
+    }
+
+    /// Whether this set is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.color.is_none() && self.modifiers.is_empty()
+    }
+}
+
+impl IntoIterator for FormattingSet {
+    type Item = Formatting;
+
+    type IntoIter = SetIter;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Formatting {
+    /// Returns an iterator over the formatting runs of the given string, yielding the
+    /// active formattings and the byte range of each run in the input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rimecraft_fmt::Formatting;
+    /// let mut iter = Formatting::span_ranges("a§lb");
+    /// let (set, range) = iter.next().unwrap();
+    /// assert!(set.is_empty());
+    /// assert_eq!(range, 0..1);
+    /// let (set, range) = iter.next().unwrap();
+    /// assert!(set.contains(Formatting::Bold));
+    /// assert_eq!(range, 4..5);
+    /// assert!(iter.next().is_none());
+    /// ```
+    #[inline]
+    pub fn span_ranges(input: &str) -> SpanRanges<'_> {
+        SpanRanges {
+            input,
+            pos: 0,
+            set: FormattingSet::new(),
+        }
+    }
+}
+
+/// The iterator returned by [`Formatting::span_ranges`].
+#[derive(Debug)]
+pub struct SpanRanges<'a> {
+    input: &'a str,
+    pos: usize,
+    set: FormattingSet,
+}
+
+impl Iterator for SpanRanges<'_> {
+    type Item = (FormattingSet, Range<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let rest = &self.input[self.pos..];
+            if rest.is_empty() {
+                return None;
+            }
+
+            if let Some(code) = rest.strip_prefix(Formatting::CODE_PREFIX) {
+                let Some(c) = code.chars().next() else {
+                    self.pos = self.input.len();
+                    return None;
+                };
+                if let Ok(formatting) = Formatting::try_from(c.to_ascii_lowercase()) {
+                    self.set.apply(formatting);
+                }
+                self.pos += Formatting::CODE_PREFIX.len_utf8() + c.len_utf8();
+                continue;
+            }
+
+            let start = self.pos;
+            self.pos = rest
+                .find(Formatting::CODE_PREFIX)
+                .map_or(self.input.len(), |i| start + i);
+            return Some((self.set, start..self.pos));
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::Formatting;
+use crate::{Formatting, FormattingFlags};
 
 #[test]
 fn check() {
@@ -9,3 +9,133 @@ fn check() {
         assert_eq!(fmt.to_string().parse::<Formatting>().unwrap(), *fmt);
     }
 }
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde_code() {
+    use serde::de::{value::CharDeserializer, value::Error, IntoDeserializer};
+
+    let de: CharDeserializer<Error> = '1'.into_deserializer();
+    assert_eq!(
+        crate::serde_code::deserialize(de).unwrap(),
+        Formatting::DarkBlue
+    );
+
+    let de: CharDeserializer<Error> = 'z'.into_deserializer();
+    assert!(crate::serde_code::deserialize(de).is_err());
+
+    #[derive(Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    struct Wrapper(#[serde(with = "crate::serde_code")] Formatting);
+
+    for fmt in Formatting::VALUES {
+        let json = serde_json::to_string(&Wrapper(*fmt)).unwrap();
+        assert_eq!(json, format!("\"{}\"", fmt.code()));
+        assert_eq!(
+            serde_json::from_str::<Wrapper>(&json).unwrap(),
+            Wrapper(*fmt)
+        );
+    }
+}
+
+#[test]
+fn span_ranges() {
+    let input = "a§c§lb§rc§";
+    let spans: Vec<_> = Formatting::span_ranges(input).collect();
+    assert_eq!(spans.len(), 3);
+
+    assert!(spans[0].0.is_empty());
+    assert_eq!(&input[spans[0].1.clone()], "a");
+
+    assert_eq!(spans[1].0.color(), Some(Formatting::Red));
+    assert!(spans[1].0.contains(Formatting::Bold));
+    assert_eq!(&input[spans[1].1.clone()], "b");
+
+    assert!(spans[2].0.is_empty());
+    assert_eq!(&input[spans[2].1.clone()], "c");
+}
+
+#[test]
+fn formatting_flags() {
+    let mut flags = FormattingFlags::from(Formatting::Bold) | FormattingFlags::ITALIC;
+    assert!(flags.contains(FormattingFlags::BOLD));
+    assert!(!flags.contains(FormattingFlags::UNDERLINE));
+    assert_eq!(
+        FormattingFlags::from(Formatting::Red),
+        FormattingFlags::EMPTY
+    );
+
+    flags.insert(Formatting::Obfuscated.into());
+    flags.remove(FormattingFlags::BOLD);
+    assert_eq!(
+        flags.iter().collect::<Vec<_>>(),
+        [Formatting::Obfuscated, Formatting::Italic]
+    );
+    assert_eq!(flags.iter().collect::<FormattingFlags>(), flags);
+}
+
+#[test]
+fn strip_codes() {
+    use std::borrow::Cow;
+
+    assert_eq!(
+        Formatting::strip_codes("§cRed §lbold§r and §Aaqua§x§"),
+        "Red bold and aqua§x§"
+    );
+    assert_eq!(Formatting::strip_codes("§ä§§1é"), "§ä§é");
+    assert!(matches!(
+        Formatting::strip_codes("plain text"),
+        Cow::Borrowed("plain text")
+    ));
+}
+
+#[test]
+fn closest_color() {
+    use rgb::RGB8;
+
+    assert_eq!(
+        Formatting::closest_color(RGB8::new(0xff, 0x55, 0x55)),
+        Formatting::Red
+    );
+    assert_eq!(
+        Formatting::closest_color(RGB8::new(0x80, 0x80, 0x80)),
+        Formatting::Gray
+    );
+    assert_eq!(
+        Formatting::closest_color(RGB8::new(0x7f, 0x7f, 0x7f)),
+        Formatting::DarkGray
+    );
+    assert_eq!(
+        Formatting::closest_color(RGB8::new(0xf0, 0xa0, 0x10)),
+        Formatting::Gold
+    );
+}
+
+#[test]
+fn parse_segments() {
+    use Formatting::*;
+
+    assert_eq!(
+        Formatting::parse_segments("§lbold §rplain").collect::<Vec<_>>(),
+        [(vec![Bold], "bold "), (vec![], "plain")]
+    );
+    assert_eq!(
+        Formatting::parse_segments("lead§c§l§oa§9b§§z").collect::<Vec<_>>(),
+        [
+            (vec![], "lead"),
+            (vec![Red, Bold, Italic], "a"),
+            (vec![Blue], "b"),
+            (vec![Blue], "z"),
+        ]
+    );
+    assert_eq!(
+        Formatting::parse_segments("a§zb").collect::<Vec<_>>(),
+        Formatting::span_ranges("a§zb")
+            .map(|(set, range)| (set.iter().collect(), &"a§zb"[range]))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        Formatting::parse_segments("a§zb").collect::<Vec<_>>(),
+        [(vec![], "a"), (vec![], "b")]
+    );
+    assert_eq!(Formatting::parse_segments("§r§l").count(), 0);
+}
@@ -1,6 +1,6 @@
 //! Extensions for [`Compound`].
 
-use std::collections::HashMap;
+use std::collections::{hash_map::Entry, HashMap};
 
 use fastnbt::{ByteArray, IntArray, LongArray, Tag, Value};
 
@@ -97,12 +97,69 @@ pub trait CompoundExt {
     /// Retrieves a slice of `Value` tags from the compound with the specified key.
     fn get_slice(&self, key: &str) -> Option<&[Value]>;
 
+    /// Retrieves a list of compounds from the compound with the specified key.
+    ///
+    /// Returns `None` if any element of the list is not a compound.
+    #[inline]
+    fn get_compound_list(&self, key: &str) -> Option<Vec<&Compound>> {
+        self.get_slice(key)?
+            .iter()
+            .map(|e| {
+                if let Value::Compound(value) = e {
+                    Some(value)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Retrieves a list of strings from the compound with the specified key.
+    ///
+    /// Returns `None` if any element of the list is not a string.
+    #[inline]
+    fn get_string_list(&self, key: &str) -> Option<Vec<&str>> {
+        self.get_slice(key)?
+            .iter()
+            .map(|e| {
+                if let Value::String(value) = e {
+                    Some(value.as_str())
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
     /// Retrieves a boolean value from the compound with the specified key.
     /// The boolean value is internally stored as an `i8` (0 for false, 1 for true).
     #[inline]
     fn get_bool(&self, key: &str) -> Option<bool> {
         self.get_i8(key).map(|e| e != 0)
     }
+
+    /// Merges the given compound into this compound recursively.
+    ///
+    /// Nested compounds present on both sides are merged key by key, while
+    /// any other value, including lists, is replaced by the one from `other`.
+    fn merge(&mut self, other: Compound);
+
+    /// Merges the given compound into this compound, overwriting top-level keys
+    /// without descending into nested compounds.
+    fn merge_shallow(&mut self, other: Compound);
+
+    /// Retrieves the value at the specified dot-separated path, like `"display.Name"`.
+    ///
+    /// Returns `None` if the path contains an empty segment, or if any
+    /// intermediate segment is missing or not a compound.
+    fn get_path(&self, path: &str) -> Option<&Value>;
+
+    /// Inserts a value at the specified dot-separated path, like `"display.Name"`,
+    /// creating missing intermediate compounds.
+    ///
+    /// Returns `false` and leaves the compound untouched if the path contains an
+    /// empty segment, or if any intermediate segment is not a compound.
+    fn insert_path(&mut self, path: &str, value: Value) -> bool;
 }
 
 impl CompoundExt for Compound {
@@ -305,4 +362,61 @@ impl CompoundExt for Compound {
             }
         })
     }
+
+    fn merge(&mut self, other: Compound) {
+        for (key, value) in other {
+            match self.entry(key) {
+                Entry::Occupied(mut entry) => match (entry.get_mut(), value) {
+                    (Value::Compound(this), Value::Compound(value)) => this.merge(value),
+                    (this, value) => *this = value,
+                },
+                Entry::Vacant(entry) => {
+                    entry.insert(value);
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn merge_shallow(&mut self, other: Compound) {
+        self.extend(other);
+    }
+
+    fn get_path(&self, path: &str) -> Option<&Value> {
+        if path.split('.').any(str::is_empty) {
+            return None;
+        }
+        let mut segments = path.split('.');
+        let last = segments.next_back()?;
+        let mut compound = self;
+        for segment in segments {
+            compound = compound.get_compound(segment)?;
+        }
+        compound.get(last)
+    }
+
+    fn insert_path(&mut self, path: &str, value: Value) -> bool {
+        if path.split('.').any(str::is_empty) {
+            return false;
+        }
+        let mut segments = path.split('.');
+        let Some(last) = segments.next_back() else {
+            return false;
+        };
+        let mut compound = self;
+        for segment in segments {
+            let Value::Compound(next) = compound
+                .entry(segment.to_owned())
+                .or_insert_with(|| Value::Compound(Compound::new()))
+            else {
+                return false;
+            };
+            compound = next;
+        }
+        compound.insert(last.to_owned(), value);
+        true
+    }
 }
+
+#[cfg(test)]
+mod tests;
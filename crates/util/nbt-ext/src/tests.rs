@@ -0,0 +1,112 @@
+use fastnbt::Value;
+
+use crate::{Compound, CompoundExt};
+
+#[test]
+fn merge_nested() {
+    let mut base = Compound::new();
+    let mut display = Compound::new();
+    display.insert_string("Name".to_owned(), "base".to_owned());
+    display.insert_i32("Color".to_owned(), 1);
+    base.insert("display".to_owned(), Value::Compound(display));
+    base.insert_i8("Count".to_owned(), 1);
+
+    let mut over = Compound::new();
+    let mut display = Compound::new();
+    display.insert_string("Name".to_owned(), "override".to_owned());
+    over.insert("display".to_owned(), Value::Compound(display));
+    over.insert_i8("Count".to_owned(), 2);
+
+    base.merge(over);
+    assert_eq!(base.get_i8("Count"), Some(2));
+    let display = base.get_compound("display").unwrap();
+    assert_eq!(display.get_str("Name"), Some("override"));
+    assert_eq!(display.get_i32("Color"), Some(1));
+}
+
+#[test]
+fn merge_replaces_lists() {
+    let mut base = Compound::new();
+    base.insert(
+        "Lore".to_owned(),
+        Value::List(vec![Value::Int(1), Value::Int(2)]),
+    );
+
+    let mut over = Compound::new();
+    over.insert("Lore".to_owned(), Value::List(vec![Value::Int(3)]));
+
+    base.merge(over);
+    assert_eq!(base.get_slice("Lore"), Some(&[Value::Int(3)][..]));
+}
+
+#[test]
+fn merge_shallow() {
+    let mut base = Compound::new();
+    let mut display = Compound::new();
+    display.insert_i32("Color".to_owned(), 1);
+    base.insert("display".to_owned(), Value::Compound(display));
+
+    let mut over = Compound::new();
+    over.insert("display".to_owned(), Value::Compound(Compound::new()));
+
+    base.merge_shallow(over);
+    assert_eq!(base.get_compound("display"), Some(&Compound::new()));
+}
+
+#[test]
+fn path() {
+    let mut compound = Compound::new();
+    assert!(compound.insert_path("display.Name", Value::String("name".to_owned())));
+    assert_eq!(
+        compound.get_compound("display").unwrap().get_str("Name"),
+        Some("name")
+    );
+    assert_eq!(
+        compound.get_path("display.Name"),
+        Some(&Value::String("name".to_owned()))
+    );
+    assert_eq!(compound.get_path("display.Lore"), None);
+
+    assert!(!compound.insert_path("display..Name", Value::Int(0)));
+    assert!(!compound.insert_path("", Value::Int(0)));
+    assert_eq!(compound.get_path("display."), None);
+}
+
+#[test]
+fn path_non_compound() {
+    let mut compound = Compound::new();
+    compound.insert_i32("display".to_owned(), 1);
+    assert_eq!(compound.get_path("display.Name"), None);
+    assert!(!compound.insert_path("display.Name", Value::Int(0)));
+    assert_eq!(compound.get_i32("display"), Some(1));
+}
+
+#[test]
+fn typed_lists() {
+    let mut compound = Compound::new();
+    compound.insert(
+        "Lore".to_owned(),
+        Value::List(vec![
+            Value::String("a".to_owned()),
+            Value::String("b".to_owned()),
+        ]),
+    );
+    compound.insert(
+        "Items".to_owned(),
+        Value::List(vec![Value::Compound(Compound::new())]),
+    );
+    compound.insert(
+        "Mixed".to_owned(),
+        Value::List(vec![Value::String("a".to_owned()), Value::Int(1)]),
+    );
+
+    assert_eq!(compound.get_string_list("Lore"), Some(vec!["a", "b"]));
+    assert_eq!(
+        compound.get_compound_list("Items"),
+        Some(vec![&Compound::new()])
+    );
+    assert_eq!(compound.get_string_list("Items"), None);
+    assert_eq!(compound.get_string_list("Mixed"), None);
+    assert_eq!(compound.get_compound_list("Mixed"), None);
+    assert_eq!(compound.get_string_list("Missing"), None);
+}
@@ -1,5 +1,3 @@
-use crate::PackedIntArray;
-
 #[derive(Debug, Clone)]
 pub(crate) struct IterInner {
     pub l: u64,
@@ -10,23 +8,26 @@ pub(crate) struct IterInner {
 /// An iterator over a packed int array.
 #[derive(Debug, Clone)]
 pub struct Iter<'a> {
-    pub(crate) array: &'a PackedIntArray,
+    pub(crate) element_bits: u32,
+    pub(crate) elements_per_long: usize,
+    pub(crate) max: u64,
     pub(crate) iter: std::slice::Iter<'a, u64>,
     pub(crate) inner: IterInner,
+    pub(crate) len: usize,
 }
 
 impl Iterator for Iter<'_> {
     type Item = u32;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.inner.times >= self.array.len() {
+        if self.inner.times >= self.len {
             return None;
         }
 
-        if self.inner.j < self.array.elements_per_long {
+        if self.inner.j < self.elements_per_long {
             self.inner.j += 1;
-            let res = self.inner.l & self.array.max;
-            self.inner.l >>= self.array.element_bits;
+            let res = self.inner.l & self.max;
+            self.inner.l >>= self.element_bits;
             self.inner.times += 1;
             Some(res as u32)
         } else {
@@ -37,7 +38,7 @@ impl Iterator for Iter<'_> {
 
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let len = self.array.len() - self.inner.times;
+        let len = self.len - self.inner.times;
         (len, Some(len))
     }
 }
@@ -45,7 +46,7 @@ impl Iterator for Iter<'_> {
 impl ExactSizeIterator for Iter<'_> {
     #[inline]
     fn len(&self) -> usize {
-        self.array.len() - self.inner.times
+        self.len - self.inner.times
     }
 }
 
@@ -64,6 +64,33 @@ impl PackedIntArray {
         Ok(this)
     }
 
+    /// Creates a new `PackedIntArray` with given `element_bits`, holding the given
+    /// values in order.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an error if `element_bits` is not in range `(0, 32]`.
+    /// - Returns an error if a value is greater than the max value of the
+    ///   element bits.
+    pub fn from_values<I>(element_bits: u32, values: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = u32>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        if !(1..=32).contains(&element_bits) {
+            return Err(Error::InvalidElementBits(element_bits));
+        }
+        let values = values.into_iter();
+        let mut this = Self::from_packed(element_bits, values.len(), None)?;
+        for (index, value) in values.enumerate() {
+            if value as u64 > this.max {
+                return Err(Error::ValueOutOfRange { index, value });
+            }
+            this.swap(index, value);
+        }
+        Ok(this)
+    }
+
     /// Creates a new `PackedIntArray` with given `element_bits`, `len` and `raw`
     /// packed data.
     ///
@@ -76,18 +103,8 @@ impl PackedIntArray {
     /// Returns an error if length of the given raw data slice is not equal to
     /// `(len + 64 / element_bits - 1) / (64 / element_bits)`.
     pub fn from_packed(element_bits: u32, len: usize, raw: Option<&[u64]>) -> Result<Self, Error> {
-        assert!(
-            0 < element_bits && element_bits <= 32,
-            "element bits should in range (0, 32]"
-        );
-
-        let max = (1u64 << element_bits) - 1;
-        let elements_per_long = 64 / element_bits as usize;
-        let i = 3 * (elements_per_long - 1);
-        let index_scale = INDEX_PARAMS[i] as isize;
-        let index_offset = INDEX_PARAMS[i + 1] as isize;
-        let index_shift = INDEX_PARAMS[i + 2] as isize;
-        let j = (len + elements_per_long - 1) / elements_per_long;
+        let layout = PackedIntArrayRef::layout(element_bits, len);
+        let j = layout.packed_len();
 
         if raw.is_some_and(|d| d.len() != j) {
             return Err(Error::InvalidLength {
@@ -98,21 +115,49 @@ impl PackedIntArray {
 
         Ok(Self {
             data: raw.map(Vec::from).unwrap_or_else(|| vec![0; j]),
-            element_bits,
-            max,
+            element_bits: layout.element_bits,
+            max: layout.max,
             len,
-            elements_per_long,
-            index_scale,
-            index_offset,
-            index_shift,
+            elements_per_long: layout.elements_per_long,
+            index_scale: layout.index_scale,
+            index_offset: layout.index_offset,
+            index_shift: layout.index_shift,
+        })
+    }
+
+    /// Creates a read-only view with given `element_bits`, `len` and `raw`
+    /// packed data, without copying the data.
+    ///
+    /// # Panics
+    ///
+    /// See [`Self::from_packed`].
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::from_packed`].
+    pub fn from_raw_borrowed(
+        element_bits: u32,
+        len: usize,
+        raw: &[u64],
+    ) -> Result<PackedIntArrayRef<'_>, Error> {
+        let layout = PackedIntArrayRef::layout(element_bits, len);
+        let j = layout.packed_len();
+        if raw.len() != j {
+            return Err(Error::InvalidLength {
+                expected: j,
+                actual: raw.len(),
+            });
+        }
+
+        Ok(PackedIntArrayRef {
+            data: raw,
+            ..layout
         })
     }
 
     #[inline]
     const fn storage_index(&self, index: usize) -> usize {
-        let l = self.index_scale as u32 as usize;
-        let m = self.index_offset as u32 as usize;
-        (index * l + m) >> 32 >> self.index_shift
+        storage_index(self.index_scale, self.index_offset, self.index_shift, index)
     }
 
     /// Sets the data at given `index` with given value and returns the old one.
@@ -159,18 +204,63 @@ impl PackedIntArray {
 
         let i = self.storage_index(index);
         let j = (index - i * self.elements_per_long) * self.element_bits as usize;
-        self.data[i] &= !(self.max << j) | (value as u64 & self.max) << j;
+        let l = &mut self.data[i];
+        *l = *l & !(self.max << j) | (value as u64 & self.max) << j;
+    }
+
+    /// Sets every element to the given value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given value is greater than the internal max value.
+    pub fn fill(&mut self, value: u32) {
+        assert!(
+            value as u64 <= self.max,
+            "given value {} could not be greater than max value {}",
+            value,
+            self.max
+        );
+
+        let bits = self.element_bits as usize;
+        let pattern = (0..self.elements_per_long)
+            .fold(0u64, |l, k| l | (value as u64 & self.max) << (k * bits));
+        let Some((last, full)) = self.data.split_last_mut() else {
+            return;
+        };
+        full.fill(pattern);
+        let rest = self.len - full.len() * self.elements_per_long;
+        *last = if rest * bits >= 64 {
+            pattern
+        } else {
+            pattern & ((1 << (rest * bits)) - 1)
+        };
+    }
+
+    /// Sets every element to zero.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.fill(0)
     }
 
     /// Gets the value at target index.
+    #[inline]
     pub fn get(&self, index: usize) -> Option<u32> {
-        if index >= self.len {
-            return None;
-        }
-        let i = self.storage_index(index);
-        let l = self.data[i];
-        let j = (index - i * self.elements_per_long) * self.element_bits as usize;
-        Some((l >> j & self.max) as u32)
+        PackedIntArrayRef::from(self).get(index)
+    }
+
+    /// Creates a copy of this array with each element taking `new_element_bits`
+    /// bits, preserving the values and the length.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an error if `new_element_bits` is not in range `(0, 32]`.
+    /// - Returns an error if a value is greater than the max value of the new
+    ///   element bits.
+    pub fn resize_bits(&self, new_element_bits: u32) -> Result<PackedIntArray, Error> {
+        Self::from_values(
+            new_element_bits,
+            (0..self.len).map(|index| self.get(index).unwrap_or_default()),
+        )
     }
 
     /// Gets the inner packed data of this array.
@@ -198,28 +288,17 @@ impl PackedIntArray {
     }
 
     /// Gets an iterator over this array.
+    #[inline]
     pub fn iter(&self) -> Iter<'_> {
-        if self.is_empty() {
-            Iter {
-                array: self,
-                iter: self.data.iter(),
-                inner: iter::IterInner {
-                    l: 0,
-                    j: self.elements_per_long,
-                    times: 0,
-                },
-            }
-        } else {
-            Iter {
-                array: self,
-                iter: self.data[1..].iter(),
-                inner: iter::IterInner {
-                    l: self.data[0],
-                    j: 0,
-                    times: 0,
-                },
-            }
-        }
+        PackedIntArrayRef::from(self).iter()
+    }
+
+    /// Gets an iterator over the decoded values of this array.
+    ///
+    /// This is the same as [`Self::iter`], named for debugging call sites.
+    #[inline]
+    pub fn debug_values(&self) -> Iter<'_> {
+        self.iter()
     }
 
     /// Gets `elements_per_long` value of this array.
@@ -239,6 +318,17 @@ impl PackedIntArray {
     pub fn element_bits(&self) -> u32 {
         self.element_bits
     }
+
+    /// Gets the `(scale, offset, shift)` parameters used for locating the long
+    /// an element is stored in.
+    ///
+    /// The storage index of an element is computed as
+    /// `(index * scale + offset) >> 32 >> shift`, with `scale` and `offset`
+    /// taken as unsigned 32-bit integers.
+    #[inline]
+    pub fn index_params(&self) -> (isize, isize, isize) {
+        (self.index_scale, self.index_offset, self.index_shift)
+    }
 }
 
 impl IntoIterator for PackedIntArray {
@@ -290,6 +380,171 @@ impl<'a> IntoIterator for &'a PackedIntArray {
     }
 }
 
+#[inline]
+const fn storage_index(scale: isize, offset: isize, shift: isize, index: usize) -> usize {
+    let l = scale as u32 as usize;
+    let m = offset as u32 as usize;
+    (index * l + m) >> 32 >> shift
+}
+
+/// A read-only view of a [`PackedIntArray`], borrowing the packed data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackedIntArrayRef<'a> {
+    data: &'a [u64],
+    element_bits: u32,
+    max: u64,
+    len: usize,
+    elements_per_long: usize,
+
+    index_scale: isize,
+    index_offset: isize,
+    index_shift: isize,
+}
+
+impl<'a> PackedIntArrayRef<'a> {
+    /// Creates a view with empty data holding the layout of the given parameters.
+    fn layout(element_bits: u32, len: usize) -> Self {
+        assert!(
+            0 < element_bits && element_bits <= 32,
+            "element bits should in range (0, 32]"
+        );
+
+        let elements_per_long = 64 / element_bits as usize;
+        let i = 3 * (elements_per_long - 1);
+        Self {
+            data: &[],
+            element_bits,
+            max: (1u64 << element_bits) - 1,
+            len,
+            elements_per_long,
+            index_scale: INDEX_PARAMS[i] as isize,
+            index_offset: INDEX_PARAMS[i + 1] as isize,
+            index_shift: INDEX_PARAMS[i + 2] as isize,
+        }
+    }
+
+    #[inline]
+    const fn packed_len(&self) -> usize {
+        self.len.div_ceil(self.elements_per_long)
+    }
+
+    /// Gets the value at target index.
+    pub fn get(&self, index: usize) -> Option<u32> {
+        if index >= self.len {
+            return None;
+        }
+        let i = storage_index(self.index_scale, self.index_offset, self.index_shift, index);
+        let l = self.data[i];
+        let j = (index - i * self.elements_per_long) * self.element_bits as usize;
+        Some((l >> j & self.max) as u32)
+    }
+
+    /// Gets the inner packed data of this array.
+    #[inline]
+    pub fn data(&self) -> &'a [u64] {
+        self.data
+    }
+
+    /// Gets the length of this array.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether this array is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Gets an iterator over this array.
+    pub fn iter(&self) -> Iter<'a> {
+        let (l, iter, j) = match self.data.split_first() {
+            Some((&first, rest)) if !self.is_empty() => (first, rest.iter(), 0),
+            _ => (0, self.data.iter(), self.elements_per_long),
+        };
+        Iter {
+            iter,
+            inner: iter::IterInner { l, j, times: 0 },
+            element_bits: self.element_bits,
+            elements_per_long: self.elements_per_long,
+            max: self.max,
+            len: self.len,
+        }
+    }
+
+    /// Gets `element_bits` value of this array.
+    #[inline]
+    pub fn element_bits(&self) -> u32 {
+        self.element_bits
+    }
+}
+
+/// Maximum number of values printed by the `Display` implementations,
+/// after which the remaining count is printed instead.
+const DISPLAY_LIMIT: usize = 32;
+
+impl std::fmt::Display for PackedIntArrayRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "bits={} len={} [", self.element_bits, self.len)?;
+        for (i, value) in self.iter().take(DISPLAY_LIMIT).enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{value}")?;
+        }
+        if self.len > DISPLAY_LIMIT {
+            write!(f, ", ... ({} more)", self.len - DISPLAY_LIMIT)?;
+        }
+        f.write_str("]")
+    }
+}
+
+impl std::fmt::Display for PackedIntArray {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&PackedIntArrayRef::from(self), f)
+    }
+}
+
+impl<'a> From<&'a PackedIntArray> for PackedIntArrayRef<'a> {
+    #[inline]
+    fn from(value: &'a PackedIntArray) -> Self {
+        Self {
+            data: &value.data,
+            element_bits: value.element_bits,
+            max: value.max,
+            len: value.len,
+            elements_per_long: value.elements_per_long,
+            index_scale: value.index_scale,
+            index_offset: value.index_offset,
+            index_shift: value.index_shift,
+        }
+    }
+}
+
+impl<'a> IntoIterator for PackedIntArrayRef<'a> {
+    type Item = u32;
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &PackedIntArrayRef<'a> {
+    type Item = u32;
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 /// Error type for `PackedIntArray`.
 #[derive(Debug)]
 #[non_exhaustive]
@@ -301,6 +556,15 @@ pub enum Error {
         /// Actual length.
         actual: usize,
     },
+    /// Element bits out of range `(0, 32]`.
+    InvalidElementBits(u32),
+    /// A value does not fit into the element bits.
+    ValueOutOfRange {
+        /// Index of the value.
+        index: usize,
+        /// The value.
+        value: u32,
+    },
 }
 
 impl std::fmt::Display for Error {
@@ -313,6 +577,16 @@ impl std::fmt::Display for Error {
                     expected, actual
                 )
             }
+            Self::InvalidElementBits(bits) => {
+                write!(f, "element bits {} out of range (0, 32]", bits)
+            }
+            Self::ValueOutOfRange { index, value } => {
+                write!(
+                    f,
+                    "value {} at index {} does not fit into element bits",
+                    value, index
+                )
+            }
         }
     }
 }
@@ -1,4 +1,4 @@
-use crate::PackedIntArray;
+use crate::{Error, PackedIntArray};
 
 #[test]
 fn swap() {
@@ -31,3 +31,126 @@ fn iter() {
     assert_eq!(iter.next(), Some(ARRAY[3]));
     assert_eq!(iter.next(), None);
 }
+
+#[test]
+fn index_params() {
+    for bits in 1..=32 {
+        let array = PackedIntArray::from_packed(bits, 100, None).expect("failed to create array");
+        let (scale, offset, shift) = array.index_params();
+        for index in 0..array.len() {
+            assert_eq!(
+                (index * scale as u32 as usize + offset as u32 as usize) >> 32 >> shift,
+                index / array.elements_per_long()
+            );
+        }
+    }
+}
+
+#[test]
+fn from_raw_borrowed() {
+    let mut array = PackedIntArray::from_packed(5, 30, None).expect("failed to create array");
+    for i in 0..array.len() {
+        array.set(i, i as u32);
+    }
+
+    let view = PackedIntArray::from_raw_borrowed(5, 30, array.data()).expect("failed to view");
+    assert_eq!(view.len(), 30);
+    assert_eq!(view.get(7), array.get(7));
+    assert_eq!(view.get(30), None);
+    assert!(view.iter().eq(array.iter()));
+
+    assert!(PackedIntArray::from_raw_borrowed(5, 37, array.data()).is_err());
+}
+
+#[test]
+fn display() {
+    let mut array = PackedIntArray::from_packed(4, 3, None).expect("failed to create array");
+    array.swap(1, 7);
+    assert_eq!(array.to_string(), "bits=4 len=3 [0, 7, 0]");
+    assert_eq!(array.debug_values().collect::<Vec<_>>(), [0, 7, 0]);
+
+    let array = PackedIntArray::from_packed(1, 40, None).expect("failed to create array");
+    assert!(array.to_string().ends_with(", 0, ... (8 more)]"));
+}
+
+#[test]
+fn resize_bits() {
+    let values: Vec<u32> = (0..40).map(|i| i % 16).collect();
+    let mut array =
+        PackedIntArray::from_packed(4, values.len(), None).expect("failed to create array");
+    for (i, &value) in values.iter().enumerate() {
+        array.swap(i, value);
+    }
+
+    let wide = array.resize_bits(8).expect("failed to resize");
+    assert_eq!(wide.element_bits(), 8);
+    assert_eq!(wide.len(), values.len());
+    assert!((0..wide.len())
+        .map(|i| wide.get(i))
+        .eq(values.iter().copied().map(Some)));
+
+    let narrow = wide.resize_bits(4).expect("failed to resize");
+    assert_eq!(narrow, array);
+
+    assert!(matches!(
+        array.resize_bits(3),
+        Err(Error::ValueOutOfRange { index: 8, value: 8 })
+    ));
+    assert!(matches!(
+        array.resize_bits(0),
+        Err(Error::InvalidElementBits(0))
+    ));
+    assert!(matches!(
+        array.resize_bits(33),
+        Err(Error::InvalidElementBits(33))
+    ));
+}
+
+#[test]
+fn from_values() {
+    let array = PackedIntArray::from_values(5, 0..30).expect("failed to create array");
+    assert_eq!(array.len(), 30);
+    assert!((0..30).all(|i| array.get(i as usize) == Some(i)));
+
+    assert!(matches!(
+        PackedIntArray::from_values(4, [1, 15, 16]),
+        Err(Error::ValueOutOfRange {
+            index: 2,
+            value: 16
+        })
+    ));
+    assert!(matches!(
+        PackedIntArray::from_values(0, []),
+        Err(Error::InvalidElementBits(0))
+    ));
+}
+
+#[test]
+fn fill() {
+    let mut array = PackedIntArray::from_values(5, 0..30).expect("failed to create array");
+    array.fill(21);
+    assert!((0..30).all(|i| array.get(i) == Some(21)));
+    let expected = PackedIntArray::from_values(5, [21; 30]).expect("failed to create array");
+    assert_eq!(array.data(), expected.data());
+    assert_eq!(array, expected);
+
+    array.clear();
+    assert!(array.data().iter().all(|&l| l == 0));
+
+    let mut empty = PackedIntArray::from_packed(4, 0, None).expect("failed to create array");
+    empty.fill(3);
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn set_keeps_neighbors() {
+    let mut array = PackedIntArray::from_packed(4, 16, None).expect("failed to create array");
+    array.set(3, 0b1010);
+    array.set(4, 0b0101);
+    assert_eq!(array.get(3), Some(0b1010));
+    assert_eq!(array.get(4), Some(0b0101));
+
+    array.set(3, 0b0110);
+    assert_eq!(array.get(3), Some(0b0110));
+    assert_eq!(array.get(4), Some(0b0101));
+}
@@ -27,6 +27,75 @@ impl<T> HumanReadableControlled<T> {
     }
 }
 
+/// Wraps the given [`Serializer`] or [`Deserializer`] with the given
+/// human-readable flag.
+///
+/// See [`HumanReadableControlled::new`].
+#[inline]
+pub const fn with_human_readable<T>(inner: T, human_readable: bool) -> HumanReadableControlled<T> {
+    HumanReadableControlled::new(inner, human_readable)
+}
+
+/// Extension trait for wrapping a [`Serializer`] fluently.
+///
+/// # Examples
+///
+/// ```
+/// # use rimecraft_serde_humanreadctl::SerializerExt;
+/// use serde::Serializer;
+///
+/// let mut buf = Vec::new();
+/// let mut serializer = serde_json::Serializer::new(&mut buf);
+/// assert!(!(&mut serializer).human_readable(false).is_human_readable());
+/// ```
+pub trait SerializerExt: Serializer {
+    /// Wraps this serializer with the given human-readable flag.
+    ///
+    /// See [`with_human_readable`].
+    fn human_readable(self, human_readable: bool) -> HumanReadableControlled<Self>;
+}
+
+impl<S> SerializerExt for S
+where
+    S: Serializer,
+{
+    #[inline]
+    fn human_readable(self, human_readable: bool) -> HumanReadableControlled<Self> {
+        with_human_readable(self, human_readable)
+    }
+}
+
+/// Extension trait for wrapping a [`Deserializer`] fluently.
+///
+/// # Examples
+///
+/// ```
+/// # use rimecraft_serde_humanreadctl::DeserializerExt;
+/// use serde::de::{
+///     value::{Error, StrDeserializer},
+///     Deserializer,
+/// };
+///
+/// let deserializer = StrDeserializer::<'_, Error>::new("value");
+/// assert!(!deserializer.human_readable(false).is_human_readable());
+/// ```
+pub trait DeserializerExt<'de>: Deserializer<'de> {
+    /// Wraps this deserializer with the given human-readable flag.
+    ///
+    /// See [`with_human_readable`].
+    fn human_readable(self, human_readable: bool) -> HumanReadableControlled<Self>;
+}
+
+impl<'de, D> DeserializerExt<'de> for D
+where
+    D: Deserializer<'de>,
+{
+    #[inline]
+    fn human_readable(self, human_readable: bool) -> HumanReadableControlled<Self> {
+        with_human_readable(self, human_readable)
+    }
+}
+
 macro_rules! ser {
     ($($f:ident, $t:ty),*$(,)?) => {
         $(
@@ -354,3 +423,6 @@ where
         self.human_readable
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,96 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::HumanReadableControlled;
+
+/// A position serialized as a string when human-readable, or as a tuple otherwise.
+#[derive(Debug, PartialEq)]
+struct Pos {
+    x: i32,
+    y: i32,
+}
+
+impl Serialize for Pos {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&format_args!("{},{}", self.x, self.y))
+        } else {
+            (self.x, self.y).serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Pos {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            let (x, y) = s
+                .split_once(',')
+                .ok_or_else(|| serde::de::Error::custom("expected `x,y`"))?;
+            Ok(Self {
+                x: x.parse().map_err(serde::de::Error::custom)?,
+                y: y.parse().map_err(serde::de::Error::custom)?,
+            })
+        } else {
+            let (x, y) = <(i32, i32)>::deserialize(deserializer)?;
+            Ok(Self { x, y })
+        }
+    }
+}
+
+#[test]
+fn round_trip() {
+    let pos = Pos { x: 1, y: -2 };
+
+    let mut buf = Vec::new();
+    pos.serialize(HumanReadableControlled::new(
+        &mut serde_json::Serializer::new(&mut buf),
+        false,
+    ))
+    .unwrap();
+    assert_eq!(buf, b"[1,-2]");
+
+    let mut json = serde_json::Deserializer::from_slice(&buf);
+    let de = HumanReadableControlled::new(&mut json, false);
+    assert!(!de.is_human_readable());
+    assert_eq!(Pos::deserialize(de).unwrap(), pos);
+
+    assert!(Pos::deserialize(&mut serde_json::Deserializer::from_slice(&buf)).is_err());
+}
+
+#[test]
+fn forwarding() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Shape {
+        Unit,
+        Tuple(i32, i32),
+        Struct { a: i32 },
+    }
+
+    for shape in [Shape::Unit, Shape::Tuple(1, 2), Shape::Struct { a: 3 }] {
+        let json = serde_json::to_string(&shape).unwrap();
+        let mut json = serde_json::Deserializer::from_str(&json);
+        let de = HumanReadableControlled::new(&mut json, false);
+        assert_eq!(Shape::deserialize(de).unwrap(), shape);
+    }
+}
+
+#[test]
+fn fluent() {
+    use crate::{with_human_readable, DeserializerExt, SerializerExt};
+
+    let mut buf = Vec::new();
+    let mut json = serde_json::Serializer::new(&mut buf);
+    assert!(!(&mut json).human_readable(false).is_human_readable());
+    assert!(with_human_readable(&mut json, true).is_human_readable());
+
+    let mut json = serde_json::Deserializer::from_str("[1,-2]");
+    let de = (&mut json).human_readable(false);
+    assert!(!de.is_human_readable());
+    assert_eq!(Pos::deserialize(de).unwrap(), Pos { x: 1, y: -2 });
+}
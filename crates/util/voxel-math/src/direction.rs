@@ -2,7 +2,7 @@
 
 use std::fmt;
 
-use glam::IVec3;
+use glam::{DVec3, IVec3};
 
 macro_rules! directions {
     ($($i:literal => $dir:ident: $doc:literal {
@@ -183,6 +183,24 @@ directions! {
     },
 }
 
+impl Direction {
+    /// Gets the direction closest to the given vector.
+    ///
+    /// Returns [`Direction::North`] if the vector is zero.
+    pub fn from_vector(vec: DVec3) -> Self {
+        let mut result = Self::North;
+        let mut max = 0.0;
+        for dir in Self::ALL {
+            let dot = vec.dot(IVec3::from(dir).as_dvec3());
+            if dot > max {
+                max = dot;
+                result = dir;
+            }
+        }
+        result
+    }
+}
+
 impl From<(AxisDirection, Axis)> for Direction {
     #[inline]
     fn from((ad, a): (AxisDirection, Axis)) -> Self {
@@ -246,6 +264,46 @@ impl AxisDirection {
     }
 }
 
+/// A rotation of a block around the Y axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[allow(clippy::exhaustive_enums)]
+#[repr(u8)]
+pub enum BlockRotation {
+    /// No rotation.
+    #[default]
+    None,
+    /// A clockwise rotation by 90 degrees, looking down.
+    Clockwise90,
+    /// A rotation by 180 degrees.
+    Clockwise180,
+    /// A counterclockwise rotation by 90 degrees, looking down.
+    Counterclockwise90,
+}
+
+impl BlockRotation {
+    /// Rotates the given direction.
+    ///
+    /// Vertical directions are unchanged.
+    pub const fn rotate(self, dir: Direction) -> Direction {
+        const fn clockwise(dir: Direction) -> Direction {
+            match dir {
+                Direction::North => Direction::East,
+                Direction::East => Direction::South,
+                Direction::South => Direction::West,
+                Direction::West => Direction::North,
+                vertical => vertical,
+            }
+        }
+
+        match self {
+            BlockRotation::None => dir,
+            BlockRotation::Clockwise90 => clockwise(dir),
+            BlockRotation::Clockwise180 => clockwise(clockwise(dir)),
+            BlockRotation::Counterclockwise90 => clockwise(clockwise(clockwise(dir))),
+        }
+    }
+}
+
 /// An enum representing 4 cardinal directions.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[allow(clippy::exhaustive_enums)]
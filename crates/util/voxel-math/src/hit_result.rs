@@ -0,0 +1,18 @@
+//! Raycast hit results.
+
+use glam::DVec3;
+
+use crate::{direction::Direction, BlockPos};
+
+/// The result of a ray hitting a block.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlockHitResult {
+    /// The position where the ray hit.
+    pub pos: DVec3,
+    /// The side of the block that was hit.
+    pub side: Direction,
+    /// The position of the block that was hit.
+    pub block_pos: BlockPos,
+    /// Whether the ray started inside the block.
+    pub inside_block: bool,
+}
@@ -10,8 +10,10 @@ pub use chunk_section_pos::ChunkSectionPos;
 
 mod bbox;
 pub mod direction;
+mod hit_result;
 
 pub use bbox::BBox;
+pub use hit_result::BlockHitResult;
 
 pub use glam::{DVec3, IVec3};
 
@@ -0,0 +1,464 @@
+//! Functions creating and combining voxel shapes.
+
+use std::{
+    ops::Range,
+    sync::{Arc, OnceLock},
+};
+
+use rimecraft_voxel_math::{direction::Axis, BBox};
+
+use crate::{
+    set::{Props, VoxelSet},
+    Array, RawVoxelShape, Simple, VoxelShapeSlice, EPSILON, MAX_SHAPE_RESOLUTION,
+};
+
+/// Returns the empty shape.
+pub fn empty() -> Arc<VoxelShapeSlice<'static>> {
+    static EMPTY: OnceLock<Arc<VoxelShapeSlice<'static>>> = OnceLock::new();
+    EMPTY
+        .get_or_init(|| {
+            Arc::from(
+                Array {
+                    raw: RawVoxelShape {
+                        voxels: VoxelSet::new(Props {
+                            len_x: 0,
+                            len_y: 0,
+                            len_z: 0,
+                        }),
+                        shape_cache: Vec::new(),
+                        bbox_cache: OnceLock::new(),
+                    },
+                    xp: Box::new([0.0]),
+                    yp: Box::new([0.0]),
+                    zp: Box::new([0.0]),
+                }
+                .into_boxed_slice(),
+            )
+        })
+        .clone()
+}
+
+/// Returns the shape of a full cube.
+pub fn full_cube() -> Arc<VoxelShapeSlice<'static>> {
+    static FULL_CUBE: OnceLock<Arc<VoxelShapeSlice<'static>>> = OnceLock::new();
+    FULL_CUBE
+        .get_or_init(|| {
+            let mut voxels = VoxelSet::new(Props {
+                len_x: 1,
+                len_y: 1,
+                len_z: 1,
+            });
+            voxels.set(0, 0, 0);
+            Arc::from(
+                Simple(RawVoxelShape {
+                    voxels,
+                    shape_cache: Vec::new(),
+                    bbox_cache: OnceLock::new(),
+                })
+                .into_boxed_slice(),
+            )
+        })
+        .clone()
+}
+
+/// Returns the union of all given shapes.
+///
+/// Empty shapes are skipped, and the empty shape is returned if there is no
+/// non-empty shape. Point positions of all shapes are merged at once, so this
+/// is cheaper than folding the shapes pairwise.
+pub fn union_all<I>(shapes: I) -> Arc<VoxelShapeSlice<'static>>
+where
+    I: IntoIterator<Item = Arc<VoxelShapeSlice<'static>>>,
+{
+    let mut shapes: Vec<_> = shapes.into_iter().filter(|s| !s.is_empty()).collect();
+    if shapes.len() <= 1 {
+        return shapes.pop().unwrap_or_else(empty);
+    }
+
+    let points = merge_points(shapes.iter().map(|shape| &**shape));
+    let mut voxels = merged_set(&points);
+
+    for shape in &shapes {
+        let set = &shape.inner.as_raw().voxels;
+        let cells = |axis: Axis, index: u32| {
+            cell_range(
+                &points[axis as usize],
+                shape.inner.index_point_pos(axis, index),
+                shape.inner.index_point_pos(axis, index + 1),
+            )
+        };
+        for x in 0..set.len_of(Axis::X) {
+            for y in 0..set.len_of(Axis::Y) {
+                for z in 0..set.len_of(Axis::Z) {
+                    if !set.contains(x, y, z) {
+                        continue;
+                    }
+                    for i in cells(Axis::X, x) {
+                        for j in cells(Axis::Y, y) {
+                            for k in cells(Axis::Z, z) {
+                                voxels.set(i, j, k);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    merged_shape(voxels, points)
+}
+
+/// A boolean operation combining the voxels of two shapes.
+///
+/// The operation receives whether a point is inside the first and the second
+/// shape respectively, and returns whether the point is inside the result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(clippy::exhaustive_enums)]
+pub enum BooleanOp {
+    /// Always `false`.
+    False,
+    /// `!(a || b)`.
+    NotOr,
+    /// `b && !a`.
+    OnlySecond,
+    /// `!a`.
+    NotFirst,
+    /// `a && !b`.
+    OnlyFirst,
+    /// `!b`.
+    NotSecond,
+    /// `a != b`.
+    NotSame,
+    /// `!(a && b)`.
+    NotAnd,
+    /// `a && b`.
+    And,
+    /// `a == b`.
+    Same,
+    /// `b`.
+    Second,
+    /// `!a || b`.
+    Causes,
+    /// `a`.
+    First,
+    /// `a || !b`.
+    CausedBy,
+    /// `a || b`.
+    Or,
+    /// Always `true`.
+    True,
+}
+
+impl BooleanOp {
+    /// Applies this operation.
+    pub const fn apply(self, a: bool, b: bool) -> bool {
+        match self {
+            BooleanOp::False => false,
+            BooleanOp::NotOr => !a && !b,
+            BooleanOp::OnlySecond => b && !a,
+            BooleanOp::NotFirst => !a,
+            BooleanOp::OnlyFirst => a && !b,
+            BooleanOp::NotSecond => !b,
+            BooleanOp::NotSame => a != b,
+            BooleanOp::NotAnd => !a || !b,
+            BooleanOp::And => a && b,
+            BooleanOp::Same => a == b,
+            BooleanOp::Second => b,
+            BooleanOp::Causes => !a || b,
+            BooleanOp::First => a,
+            BooleanOp::CausedBy => a || !b,
+            BooleanOp::Or => a || b,
+            BooleanOp::True => true,
+        }
+    }
+}
+
+/// Returns the union of the two shapes.
+#[inline]
+pub fn union(a: &VoxelShapeSlice<'_>, b: &VoxelShapeSlice<'_>) -> Arc<VoxelShapeSlice<'static>> {
+    combine(a, b, BooleanOp::Or)
+}
+
+/// Combines the two shapes with the given boolean operation.
+///
+/// Point positions of both shapes are merged along each axis, so shapes of
+/// different resolutions are combined without loss.
+///
+/// # Panics
+///
+/// Panics if the operation yields `true` for points outside both shapes,
+/// as the result would be infinite.
+pub fn combine(
+    a: &VoxelShapeSlice<'_>,
+    b: &VoxelShapeSlice<'_>,
+    op: BooleanOp,
+) -> Arc<VoxelShapeSlice<'static>> {
+    assert!(
+        !op.apply(false, false),
+        "operation {op:?} yields an infinite shape"
+    );
+
+    let points = merge_points([a, b].into_iter().filter(|shape| !shape.is_empty()));
+    let mut voxels = merged_set(&points);
+    let [ca, cb] = [a, b].map(|shape| {
+        [Axis::X, Axis::Y, Axis::Z].map(|axis| cell_indices(shape, axis, &points[axis as usize]))
+    });
+    let contains = |shape: &VoxelShapeSlice<'_>, cells: &[Vec<Option<u32>>; 3], i, j, k| {
+        let (Some(x), Some(y), Some(z)) = (
+            cells[0][i as usize],
+            cells[1][j as usize],
+            cells[2][k as usize],
+        ) else {
+            return false;
+        };
+        shape.inner.as_raw().voxels.contains(x, y, z)
+    };
+
+    let mut any = false;
+    for i in 0..voxels.len_of(Axis::X) {
+        for j in 0..voxels.len_of(Axis::Y) {
+            for k in 0..voxels.len_of(Axis::Z) {
+                if op.apply(contains(a, &ca, i, j, k), contains(b, &cb, i, j, k)) {
+                    voxels.set(i, j, k);
+                    any = true;
+                }
+            }
+        }
+    }
+
+    if any {
+        merged_shape(voxels, points)
+    } else {
+        empty()
+    }
+}
+
+/// Merges point positions of the given shapes along each axis, deduplicating
+/// points closer than [`EPSILON`].
+fn merge_points<'s, 'a: 's, I>(shapes: I) -> [Box<[f64]>; 3]
+where
+    I: IntoIterator<Item = &'s VoxelShapeSlice<'a>>,
+{
+    let shapes: Vec<_> = shapes.into_iter().collect();
+    [Axis::X, Axis::Y, Axis::Z].map(|axis| {
+        let mut points: Vec<f64> = shapes
+            .iter()
+            .flat_map(|shape| shape.inner.point_poss(axis))
+            .collect();
+        points.sort_by(f64::total_cmp);
+        points.dedup_by(|a, b| (*a - *b).abs() < EPSILON);
+        points.into_boxed_slice()
+    })
+}
+
+/// Creates an empty voxel set with a cell between each pair of merged points.
+fn merged_set(points: &[Box<[f64]>; 3]) -> VoxelSet {
+    let len = |axis: Axis| points[axis as usize].len().saturating_sub(1) as u32;
+    VoxelSet::new(Props {
+        len_x: len(Axis::X),
+        len_y: len(Axis::Y),
+        len_z: len(Axis::Z),
+    })
+}
+
+fn merged_shape(voxels: VoxelSet, points: [Box<[f64]>; 3]) -> Arc<VoxelShapeSlice<'static>> {
+    let [xp, yp, zp] = points;
+    Arc::from(
+        Array {
+            raw: RawVoxelShape {
+                voxels,
+                shape_cache: Vec::new(),
+                bbox_cache: OnceLock::new(),
+            },
+            xp,
+            yp,
+            zp,
+        }
+        .into_boxed_slice(),
+    )
+}
+
+/// Maps each cell of merged `points` to the cell of the shape containing it.
+fn cell_indices(shape: &VoxelShapeSlice<'_>, axis: Axis, points: &[f64]) -> Vec<Option<u32>> {
+    let own: Vec<f64> = shape.inner.point_poss(axis).collect();
+    points
+        .windows(2)
+        .map(|w| {
+            let mid = (w[0] + w[1]) / 2.0;
+            let i = own.partition_point(|&p| p <= mid);
+            (i > 0 && i < own.len()).then(|| i as u32 - 1)
+        })
+        .collect()
+}
+
+/// Rasterizes the given box into a shape of `resolution` cells on each axis,
+/// filling every cell overlapping the box.
+///
+/// The part of the box outside of the unit cube is ignored, and the empty shape
+/// is returned if nothing is left.
+///
+/// # Panics
+///
+/// Panics if `resolution` is zero or exceeds [`MAX_SHAPE_RESOLUTION`].
+pub fn rasterize(bbox: BBox, resolution: u32) -> Arc<VoxelShapeSlice<'static>> {
+    assert!(
+        (1..=MAX_SHAPE_RESOLUTION).contains(&resolution),
+        "resolution {resolution} is out of range 1..={MAX_SHAPE_RESOLUTION}"
+    );
+
+    let res = resolution as f64;
+    let cells = [Axis::X, Axis::Y, Axis::Z].map(|axis| {
+        let min = (bbox.min()[axis as usize] * res + EPSILON).floor().max(0.0);
+        let max = (bbox.max()[axis as usize] * res - EPSILON).ceil().min(res);
+        min as u32..max as u32
+    });
+    if cells.iter().any(Range::is_empty) {
+        return empty();
+    }
+
+    let mut voxels = VoxelSet::new(Props {
+        len_x: resolution,
+        len_y: resolution,
+        len_z: resolution,
+    });
+    let [xs, ys, zs] = cells;
+    for x in xs {
+        for y in ys.clone() {
+            for z in zs.clone() {
+                voxels.set(x, y, z);
+            }
+        }
+    }
+    Arc::from(
+        Simple(RawVoxelShape {
+            voxels,
+            shape_cache: Vec::new(),
+            bbox_cache: OnceLock::new(),
+        })
+        .into_boxed_slice(),
+    )
+}
+
+/// Creates a shape of the given box.
+///
+/// See [`from_boxes`].
+#[inline]
+pub fn cuboid(bbox: BBox) -> Arc<VoxelShapeSlice<'static>> {
+    from_boxes([bbox])
+}
+
+/// Creates a shape covering all given boxes.
+///
+/// If every box lies within the unit cube with its boundaries on a grid of
+/// at most [`MAX_SHAPE_RESOLUTION`] cells, the boxes are rasterized onto the
+/// coarsest common grid. Otherwise, including when a box extends beyond the
+/// unit cube, each box keeps its exact coordinates and the boxes are merged
+/// with [`union_all`].
+///
+/// Boxes without volume are skipped, and the empty shape is returned if there
+/// is no box left.
+pub fn from_boxes<I>(boxes: I) -> Arc<VoxelShapeSlice<'static>>
+where
+    I: IntoIterator<Item = BBox>,
+{
+    let boxes: Vec<BBox> = boxes
+        .into_iter()
+        .filter(|bbox| (bbox.max() - bbox.min()).min_element() >= EPSILON)
+        .collect();
+    if boxes.is_empty() {
+        return empty();
+    }
+
+    let resolutions = [Axis::X, Axis::Y, Axis::Z].map(|axis| {
+        boxes.iter().try_fold(1, |res, bbox| {
+            required_resolution(bbox.min()[axis as usize], bbox.max()[axis as usize])
+                .map(|r| r.max(res))
+        })
+    });
+    let [Some(len_x), Some(len_y), Some(len_z)] = resolutions else {
+        return union_all(boxes.into_iter().map(exact_cuboid));
+    };
+    if len_x == 1 && len_y == 1 && len_z == 1 {
+        return full_cube();
+    }
+
+    let props = Props {
+        len_x,
+        len_y,
+        len_z,
+    };
+    let mut voxels = VoxelSet::new(props);
+    for bbox in boxes {
+        let [xs, ys, zs] = [Axis::X, Axis::Y, Axis::Z].map(|axis| {
+            let res = voxels.len_of(axis) as f64;
+            let cell = |coord: f64| (coord * res).round() as u32;
+            cell(bbox.min()[axis as usize])..cell(bbox.max()[axis as usize])
+        });
+        for x in xs {
+            for y in ys.clone() {
+                for z in zs.clone() {
+                    voxels.set(x, y, z);
+                }
+            }
+        }
+    }
+    Arc::from(
+        Simple(RawVoxelShape {
+            voxels,
+            shape_cache: Vec::new(),
+            bbox_cache: OnceLock::new(),
+        })
+        .into_boxed_slice(),
+    )
+}
+
+/// Gets the coarsest resolution of the unit interval with both `min` and `max`
+/// on its grid, or `None` if there is no such resolution within
+/// [`MAX_SHAPE_RESOLUTION`].
+fn required_resolution(min: f64, max: f64) -> Option<u32> {
+    if min < -EPSILON || max > 1.0 + EPSILON {
+        return None;
+    }
+    (0..=MAX_SHAPE_RESOLUTION.ilog2())
+        .map(|bits| 1 << bits)
+        .find(|&res| {
+            let res = res as f64;
+            [min, max]
+                .into_iter()
+                .all(|coord| (coord * res - (coord * res).round()).abs() < EPSILON * res)
+        })
+}
+
+/// Creates a single-voxel shape spanning exactly the given box.
+fn exact_cuboid(bbox: BBox) -> Arc<VoxelShapeSlice<'static>> {
+    let mut voxels = VoxelSet::new(Props {
+        len_x: 1,
+        len_y: 1,
+        len_z: 1,
+    });
+    voxels.set(0, 0, 0);
+    let (min, max) = (bbox.min(), bbox.max());
+    Arc::from(
+        Array {
+            raw: RawVoxelShape {
+                voxels,
+                shape_cache: Vec::new(),
+                bbox_cache: OnceLock::new(),
+            },
+            xp: Box::new([min.x, max.x]),
+            yp: Box::new([min.y, max.y]),
+            zp: Box::new([min.z, max.z]),
+        }
+        .into_boxed_slice(),
+    )
+}
+
+/// Gets the range of cells of merged `points` lying between `min` and `max`.
+fn cell_range(points: &[f64], min: Option<f64>, max: Option<f64>) -> Range<u32> {
+    let (Some(min), Some(max)) = (min, max) else {
+        return 0..0;
+    };
+    let start = points.partition_point(|&p| p < min - EPSILON);
+    let end = points.partition_point(|&p| p < max - EPSILON);
+    start as u32..end as u32
+}
@@ -1,15 +1,26 @@
 //! Minecraft voxel shapes.
 
+pub mod func;
 pub mod set;
 
 use std::{
+    convert::Infallible,
     fmt::Debug,
     ops::{Deref, DerefMut},
-    sync::Arc,
+    sync::{Arc, OnceLock},
 };
 
-use rimecraft_voxel_math::direction::Axis;
-use set::VoxelSet;
+use rimecraft_voxel_math::{
+    direction::{Axis, AxisDirection, BlockRotation, Direction},
+    BBox, BlockHitResult, BlockPos, DVec3,
+};
+use set::{Props, VoxelSet};
+
+/// The maximum resolution of a voxel shape along each axis.
+pub const MAX_SHAPE_RESOLUTION: u32 = 8;
+
+/// Tolerance used when comparing point positions.
+const EPSILON: f64 = 1.0e-7;
 
 trait AbstVoxelShape {
     fn as_raw(&self) -> &RawVoxelShape;
@@ -17,6 +28,13 @@ trait AbstVoxelShape {
 
     fn index_point_pos(&self, axis: Axis, index: u32) -> Option<f64>;
     fn point_poss<'a>(&'a self, axis: Axis) -> Box<dyn Iterator<Item = f64> + 'a>;
+
+    /// The bounding box cache of this shape, or `None` if the raw shape is
+    /// shared with shapes of different point positions.
+    #[inline]
+    fn bbox_cache(&self) -> Option<&OnceLock<Option<BBox>>> {
+        Some(&self.as_raw().bbox_cache)
+    }
 }
 
 /// Slice of a `VoxelShape`.
@@ -25,13 +43,13 @@ pub struct VoxelShapeSlice<'a> {
     inner: dyn AbstVoxelShape + Send + Sync + 'a,
 }
 
-impl VoxelShapeSlice<'_> {
+impl<'a> VoxelShapeSlice<'a> {
     /// Returns the minimum coordinate of the shape along the given axis.
     pub fn min(&self, axis: Axis) -> f64 {
         let voxels = &self.inner.as_raw().voxels;
         let i = *voxels.bounds_of(axis).start();
 
-        (i >= voxels.len_of(axis))
+        (i < voxels.len_of(axis))
             .then(|| self.inner.index_point_pos(axis, i))
             .flatten()
             .unwrap_or(f64::INFINITY)
@@ -42,11 +60,446 @@ impl VoxelShapeSlice<'_> {
         let voxels = &self.inner.as_raw().voxels;
         let i = *voxels.bounds_of(axis).end();
 
-        (i >= voxels.len_of(axis))
+        (i > 0)
             .then(|| self.inner.index_point_pos(axis, i))
             .flatten()
             .unwrap_or(f64::NEG_INFINITY)
     }
+
+    /// Whether this shape contains no voxels.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inner.as_raw().voxels.is_empty()
+    }
+
+    /// Whether this shape fills the whole block, like [`func::full_cube`].
+    pub fn is_full_cube(&self) -> bool {
+        if std::ptr::addr_eq(self, &*func::full_cube()) {
+            return true;
+        }
+        let mut boxes = self.boxes();
+        boxes.next() == Some(BBox::new(DVec3::ZERO, DVec3::ONE)) && boxes.next().is_none()
+    }
+
+    /// Returns the minimum and maximum corners of the shape, or `None` if
+    /// the shape is empty.
+    pub fn extents(&self) -> Option<(DVec3, DVec3)> {
+        if self.is_empty() {
+            return None;
+        }
+        let voxels = &self.inner.as_raw().voxels;
+        let mut min = DVec3::ZERO;
+        let mut max = DVec3::ZERO;
+        for axis in [Axis::X, Axis::Y, Axis::Z] {
+            let bounds = voxels.bounds_of(axis);
+            min[axis as usize] = self.inner.index_point_pos(axis, *bounds.start())?;
+            max[axis as usize] = self.inner.index_point_pos(axis, *bounds.end())?;
+        }
+        Some((min, max))
+    }
+
+    /// Returns the bounding box of this shape, or `None` if the shape is empty.
+    ///
+    /// The result is computed once and cached.
+    pub fn bounding_box(&self) -> Option<BBox> {
+        let compute = || self.extents().map(|(min, max)| BBox::new(min, max));
+        match self.inner.bbox_cache() {
+            Some(cache) => *cache.get_or_init(compute),
+            None => compute(),
+        }
+    }
+
+    /// Returns the boxes composing this shape, with adjacent voxels greedily merged
+    /// into maximal boxes.
+    pub fn optimized_boxes(&self) -> Vec<BBox> {
+        let mut boxes = Vec::new();
+        self.for_each_box(|bbox| boxes.push(bbox));
+        boxes
+    }
+
+    /// Returns an iterator over the boxes composing this shape.
+    ///
+    /// See [`Self::optimized_boxes`].
+    #[inline]
+    pub fn boxes(&self) -> std::vec::IntoIter<BBox> {
+        self.optimized_boxes().into_iter()
+    }
+
+    /// Visits the boxes composing this shape like [`Self::optimized_boxes`],
+    /// without collecting them.
+    ///
+    /// Adjacent voxels are merged along the X axis first, then Y, then Z.
+    pub fn for_each_box<F>(&self, mut f: F)
+    where
+        F: FnMut(BBox),
+    {
+        let Ok(()) = self.try_for_each_box(|bbox| {
+            f(bbox);
+            Ok::<_, Infallible>(())
+        });
+    }
+
+    /// Visits the boxes composing this shape like [`Self::optimized_boxes`],
+    /// stopping at the first error returned by the visitor.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error returned by the visitor.
+    pub fn try_for_each_box<E, F>(&self, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(BBox) -> Result<(), E>,
+    {
+        self.inner
+            .as_raw()
+            .voxels
+            .try_for_each_box(|min, max| self.index_box(min, max).map_or(Ok(()), &mut f))
+    }
+
+    fn index_box(&self, min: [u32; 3], max: [u32; 3]) -> Option<BBox> {
+        let corner = |pos: [u32; 3]| {
+            Some(DVec3::new(
+                self.inner.index_point_pos(Axis::X, pos[0])?,
+                self.inner.index_point_pos(Axis::Y, pos[1])?,
+                self.inner.index_point_pos(Axis::Z, pos[2])?,
+            ))
+        };
+        Some(BBox::new(corner(min)?, corner(max)?))
+    }
+
+    /// Traces a ray from `start` to `end` against this shape placed at `pos`,
+    /// returning the nearest hit.
+    ///
+    /// If the ray starts inside the shape, the hit is reported just past the
+    /// start, facing against the ray, with [`BlockHitResult::inside_block`] set.
+    pub fn raycast(&self, start: DVec3, end: DVec3, pos: BlockPos) -> Option<BlockHitResult> {
+        if self.is_empty() {
+            return None;
+        }
+        let delta = end - start;
+        if delta.length_squared() < EPSILON {
+            return None;
+        }
+
+        let origin = pos.0.as_dvec3();
+        let probe = start + delta * 0.001;
+        if self.contains_point(probe - origin) {
+            return Some(BlockHitResult {
+                pos: probe,
+                side: Direction::from_vector(delta).opposite(),
+                block_pos: pos,
+                inside_block: true,
+            });
+        }
+
+        let mut nearest = 1.0;
+        let mut side = None;
+        self.for_each_box(|bbox| {
+            trace_box(
+                bbox.min() + origin,
+                bbox.max() + origin,
+                start,
+                delta,
+                &mut nearest,
+                &mut side,
+            )
+        });
+        side.map(|side| BlockHitResult {
+            pos: start + delta * nearest,
+            side,
+            block_pos: pos,
+            inside_block: false,
+        })
+    }
+
+    /// Whether the given point is inside a voxel of this shape.
+    fn contains_point(&self, point: DVec3) -> bool {
+        let voxels = &self.inner.as_raw().voxels;
+        let index = |axis: Axis| {
+            u32::try_from(self.coord_index(axis, point[axis as usize]))
+                .ok()
+                .filter(|&i| i < voxels.len_of(axis))
+        };
+        matches!(
+            (index(Axis::X), index(Axis::Y), index(Axis::Z)),
+            (Some(x), Some(y), Some(z)) if voxels.contains(x, y, z)
+        )
+    }
+
+    /// Returns the index of the cell containing the given coordinate along the
+    /// given axis, ranging from `-1` to the resolution of the axis.
+    fn coord_index(&self, axis: Axis, coord: f64) -> i64 {
+        self.inner
+            .point_poss(axis)
+            .take_while(|&p| p <= coord)
+            .count() as i64
+            - 1
+    }
+
+    /// Clamps the displacement `max_dist` of the box `other` along the given
+    /// axis, so that the moved box does not penetrate this shape.
+    ///
+    /// The displacement is returned unchanged if this shape is not in the way,
+    /// including when it does not overlap the box on the other two axes.
+    pub fn calculate_max_distance(&self, axis: Axis, other: &BBox, max_dist: f64) -> f64 {
+        if self.is_empty() {
+            return max_dist;
+        }
+        if max_dist.abs() < EPSILON {
+            return 0.0;
+        }
+
+        let voxels = &self.inner.as_raw().voxels;
+        let [axis2, axis3] = match axis {
+            Axis::X => [Axis::Y, Axis::Z],
+            Axis::Y => [Axis::Z, Axis::X],
+            Axis::Z => [Axis::X, Axis::Y],
+        };
+        let (min, max) = (other.min(), other.max());
+        let (lo, hi) = (min[axis as usize], max[axis as usize]);
+        let range = |a: Axis| {
+            let start = self.coord_index(a, min[a as usize] + EPSILON).max(0);
+            let end =
+                (self.coord_index(a, max[a as usize] - EPSILON) + 1).min(voxels.len_of(a) as i64);
+            start as u32..end.max(start) as u32
+        };
+        let (range2, range3) = (range(axis2), range(axis3));
+        let blocked = |p: u32| {
+            range2.clone().any(|q| {
+                range3.clone().any(|r| {
+                    let mut pos = [0; 3];
+                    pos[axis as usize] = p;
+                    pos[axis2 as usize] = q;
+                    pos[axis3 as usize] = r;
+                    voxels.contains(pos[0], pos[1], pos[2])
+                })
+            })
+        };
+
+        let len = voxels.len_of(axis) as i64;
+        if max_dist > 0.0 {
+            let first = self.coord_index(axis, hi - EPSILON) + 1;
+            if let Some(p) = (first.max(0)..len).map(|p| p as u32).find(|&p| blocked(p)) {
+                if let Some(dist) = self.inner.index_point_pos(axis, p).map(|pos| pos - hi) {
+                    if dist >= -EPSILON {
+                        return max_dist.min(dist);
+                    }
+                }
+            }
+        } else {
+            let last = self.coord_index(axis, lo + EPSILON).min(len);
+            if let Some(p) = (0..last).rev().map(|p| p as u32).find(|&p| blocked(p)) {
+                if let Some(dist) = self.inner.index_point_pos(axis, p + 1).map(|pos| pos - lo) {
+                    if dist <= EPSILON {
+                        return max_dist.max(dist);
+                    }
+                }
+            }
+        }
+        max_dist
+    }
+
+    /// Returns a copy of this shape offset by the given vector.
+    ///
+    /// See [`Self::offset_borrowed`] for offsetting without allocation.
+    pub fn offset(&self, offset: DVec3) -> Arc<VoxelShapeSlice<'static>> {
+        let offset = Offset {
+            parent: self,
+            offset,
+        };
+        Arc::from(
+            Array {
+                raw: RawVoxelShape {
+                    voxels: self.inner.as_raw().voxels.clone(),
+                    shape_cache: Vec::new(),
+                    bbox_cache: OnceLock::new(),
+                },
+                xp: offset.point_poss(Axis::X).collect(),
+                yp: offset.point_poss(Axis::Y).collect(),
+                zp: offset.point_poss(Axis::Z).collect(),
+            }
+            .into_boxed_slice(),
+        )
+    }
+
+    /// Returns a copy of this shape rotated around the vertical center line of
+    /// the block.
+    ///
+    /// The empty shape and the full cube are returned as their shared instances.
+    pub fn rotate(&self, rotation: BlockRotation) -> Arc<VoxelShapeSlice<'static>> {
+        if self.is_empty() {
+            return func::empty();
+        }
+        let full_cube = func::full_cube();
+        if std::ptr::addr_eq(self, &*full_cube) {
+            return full_cube;
+        }
+
+        let voxels = &self.inner.as_raw().voxels;
+        let (len_x, len_z) = (voxels.len_of(Axis::X), voxels.len_of(Axis::Z));
+        let points = |axis: Axis| self.inner.point_poss(axis).collect::<Box<[f64]>>();
+        let flipped = |axis: Axis| {
+            let mut points: Box<[f64]> = self.inner.point_poss(axis).map(|p| 1.0 - p).collect();
+            points.reverse();
+            points
+        };
+        let (xp, zp) = match rotation {
+            BlockRotation::None => (points(Axis::X), points(Axis::Z)),
+            BlockRotation::Clockwise90 => (flipped(Axis::Z), points(Axis::X)),
+            BlockRotation::Clockwise180 => (flipped(Axis::X), flipped(Axis::Z)),
+            BlockRotation::Counterclockwise90 => (points(Axis::Z), flipped(Axis::X)),
+        };
+        let map = |x: u32, z: u32| match rotation {
+            BlockRotation::None => (x, z),
+            BlockRotation::Clockwise90 => (len_z - 1 - z, x),
+            BlockRotation::Clockwise180 => (len_x - 1 - x, len_z - 1 - z),
+            BlockRotation::Counterclockwise90 => (z, len_x - 1 - x),
+        };
+
+        let mut rotated = VoxelSet::new(Props {
+            len_x: (xp.len() - 1) as u32,
+            len_y: voxels.len_of(Axis::Y),
+            len_z: (zp.len() - 1) as u32,
+        });
+        for x in 0..len_x {
+            for y in 0..voxels.len_of(Axis::Y) {
+                for z in 0..len_z {
+                    if voxels.contains(x, y, z) {
+                        let (x2, z2) = map(x, z);
+                        rotated.set(x2, y, z2);
+                    }
+                }
+            }
+        }
+
+        Arc::from(
+            Array {
+                raw: RawVoxelShape {
+                    voxels: rotated,
+                    shape_cache: Vec::new(),
+                    bbox_cache: OnceLock::new(),
+                },
+                xp,
+                yp: points(Axis::Y),
+                zp,
+            }
+            .into_boxed_slice(),
+        )
+    }
+
+    /// Returns this shape offset by the given vector, borrowing this shape.
+    #[inline]
+    pub fn offset_borrowed<'s>(&'s self, offset: DVec3) -> Offset<'s, 'a> {
+        Offset {
+            parent: self,
+            offset,
+        }
+    }
+
+    /// Returns a slice of this shape holding the layer of voxels at the given index
+    /// along the given axis.
+    ///
+    /// The slice is empty if the index is out of the resolution of the axis.
+    pub fn slice(&self, axis: Axis, width: u32) -> Sliced<'_, 'a> {
+        let voxels = &self.inner.as_raw().voxels;
+        let len = |a: Axis| if a == axis { 1 } else { voxels.len_of(a) };
+        let props = Props {
+            len_x: len(Axis::X),
+            len_y: len(Axis::Y),
+            len_z: len(Axis::Z),
+        };
+        let mut set = VoxelSet::new(props);
+        if width < voxels.len_of(axis) {
+            for x in 0..props.len_x {
+                for y in 0..props.len_y {
+                    for z in 0..props.len_z {
+                        let mut pos = [x, y, z];
+                        pos[axis as usize] = width;
+                        if voxels.contains(pos[0], pos[1], pos[2]) {
+                            set.set(x, y, z);
+                        }
+                    }
+                }
+            }
+        }
+
+        Sliced {
+            parent: self,
+            shape: RawVoxelShape {
+                voxels: set,
+                shape_cache: Vec::new(),
+                bbox_cache: OnceLock::new(),
+            },
+            axis,
+        }
+    }
+
+    /// Returns a slice of this shape holding the outermost layer of voxels toward
+    /// the given direction.
+    ///
+    /// See [`Self::slice`].
+    pub fn slice_facing(&self, direction: Direction) -> Sliced<'_, 'a> {
+        let axis = Axis::from(direction);
+        let width = match AxisDirection::from(direction) {
+            AxisDirection::Positive => self.inner.as_raw().voxels.len_of(axis).saturating_sub(1),
+            AxisDirection::Negative => 0,
+        };
+        self.slice(axis, width)
+    }
+
+    /// Whether this shape fully covers the unit face toward the given direction.
+    ///
+    /// Empty shapes never cover any face.
+    pub fn covers_face(&self, direction: Direction) -> bool {
+        let voxels = &self.inner.as_raw().voxels;
+        let axis = Axis::from(direction);
+        let len = voxels.len_of(axis);
+        if len == 0 {
+            return false;
+        }
+
+        let (layer, bound, edge) = match AxisDirection::from(direction) {
+            AxisDirection::Positive => (len - 1, len, 1.0),
+            AxisDirection::Negative => (0, 0, 0.0),
+        };
+        if !self
+            .inner
+            .index_point_pos(axis, bound)
+            .is_some_and(|pos| (pos - edge).abs() < EPSILON)
+        {
+            return false;
+        }
+
+        let (a, b) = match axis {
+            Axis::X => (Axis::Y, Axis::Z),
+            Axis::Y => (Axis::X, Axis::Z),
+            Axis::Z => (Axis::X, Axis::Y),
+        };
+        let spans_unit = |axis: Axis| {
+            let len = voxels.len_of(axis);
+            len > 0
+                && self
+                    .inner
+                    .index_point_pos(axis, 0)
+                    .is_some_and(|pos| pos.abs() < EPSILON)
+                && self
+                    .inner
+                    .index_point_pos(axis, len)
+                    .is_some_and(|pos| (pos - 1.0).abs() < EPSILON)
+        };
+        if !spans_unit(a) || !spans_unit(b) {
+            return false;
+        }
+
+        (0..voxels.len_of(a)).all(|i| {
+            (0..voxels.len_of(b)).all(|j| {
+                let mut pos = [0u32; 3];
+                pos[axis as usize] = layer;
+                pos[a as usize] = i;
+                pos[b as usize] = j;
+                voxels.contains(pos[0], pos[1], pos[2])
+            })
+        })
+    }
 }
 
 impl Debug for VoxelShapeSlice<'_> {
@@ -79,6 +532,7 @@ impl<'a> VoxelShapeSlice<'a> {
 struct RawVoxelShape {
     voxels: VoxelSet,
     shape_cache: Vec<Arc<VoxelShapeSlice<'static>>>, //TODO: done these
+    bbox_cache: OnceLock<Option<BBox>>,
 }
 
 /// A simple voxel shape.
@@ -315,3 +769,236 @@ impl<'s> DerefMut for SlicedMut<'s, 's> {
         VoxelShapeSlice::from_mut(self)
     }
 }
+
+/// A voxel shape that is an offset of another shape.
+#[derive(Debug, Clone)]
+pub struct Offset<'a, 's> {
+    parent: &'a VoxelShapeSlice<'s>,
+    offset: DVec3,
+}
+
+impl<'a> Offset<'a, 'a> {
+    /// Converts the shape into a boxed slice.
+    #[inline]
+    pub fn into_boxed_slice(self) -> Box<VoxelShapeSlice<'a>> {
+        VoxelShapeSlice::from_boxed(Box::new(self))
+    }
+}
+
+impl AbstVoxelShape for Offset<'_, '_> {
+    #[inline]
+    fn as_raw(&self) -> &RawVoxelShape {
+        self.parent.inner.as_raw()
+    }
+
+    fn as_raw_mut(&mut self) -> &mut RawVoxelShape {
+        unreachable!("Offset shape is immutable")
+    }
+
+    #[inline]
+    fn bbox_cache(&self) -> Option<&OnceLock<Option<BBox>>> {
+        None
+    }
+
+    fn index_point_pos(&self, axis: Axis, index: u32) -> Option<f64> {
+        let offset = axis.choose(self.offset.x, self.offset.y, self.offset.z);
+        self.parent
+            .inner
+            .index_point_pos(axis, index)
+            .map(|pos| pos + offset)
+    }
+
+    fn point_poss<'a>(&'a self, axis: Axis) -> Box<dyn Iterator<Item = f64> + 'a> {
+        let offset = axis.choose(self.offset.x, self.offset.y, self.offset.z);
+        Box::new(
+            self.parent
+                .inner
+                .point_poss(axis)
+                .map(move |pos| pos + offset),
+        )
+    }
+}
+
+impl<'a> Deref for Offset<'a, '_> {
+    type Target = VoxelShapeSlice<'a>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        VoxelShapeSlice::from_ref(self)
+    }
+}
+
+/// Updates `nearest` and `side` if the ray `start + t * delta` enters the box
+/// at `t` within `(0, nearest)`.
+fn trace_box(
+    min: DVec3,
+    max: DVec3,
+    start: DVec3,
+    delta: DVec3,
+    nearest: &mut f64,
+    side: &mut Option<Direction>,
+) {
+    for axis in [Axis::X, Axis::Y, Axis::Z] {
+        let a = axis as usize;
+        let (face, dir) = if delta[a] > EPSILON {
+            (min[a], AxisDirection::Negative)
+        } else if delta[a] < -EPSILON {
+            (max[a], AxisDirection::Positive)
+        } else {
+            continue;
+        };
+        let t = (face - start[a]) / delta[a];
+        if t <= 0.0 || t >= *nearest {
+            continue;
+        }
+        let hit = start + delta * t;
+        let within = [Axis::X, Axis::Y, Axis::Z]
+            .into_iter()
+            .filter(|&other| other != axis)
+            .all(|other| {
+                let o = other as usize;
+                min[o] - EPSILON < hit[o] && hit[o] < max[o] + EPSILON
+            });
+        if within {
+            *nearest = t;
+            *side = Some((axis, dir).into());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "serde")]
+mod _serde {
+    use std::sync::OnceLock;
+
+    use rimecraft_voxel_math::direction::Axis;
+    use serde::{Deserialize, Serialize};
+
+    use crate::{
+        set::{Props, VoxelSet},
+        Array, RawVoxelShape, VoxelShapeSlice,
+    };
+
+    #[derive(Serialize)]
+    struct Repr {
+        x: Vec<f64>,
+        y: Vec<f64>,
+        z: Vec<f64>,
+        voxels: Vec<u64>,
+    }
+
+    impl Serialize for VoxelShapeSlice<'_> {
+        /// Serializes the shape as point positions along each axis, and its voxel
+        /// occupancy packed into longs in index order.
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            let voxels = &self.inner.as_raw().voxels;
+            let (len_x, len_y, len_z) = (
+                voxels.len_of(Axis::X),
+                voxels.len_of(Axis::Y),
+                voxels.len_of(Axis::Z),
+            );
+            let mut packed =
+                vec![0u64; ((len_x as usize) * (len_y as usize) * (len_z as usize)).div_ceil(64)];
+            let mut i = 0usize;
+            for x in 0..len_x {
+                for y in 0..len_y {
+                    for z in 0..len_z {
+                        if voxels.contains(x, y, z) {
+                            packed[i / 64] |= 1 << (i % 64);
+                        }
+                        i += 1;
+                    }
+                }
+            }
+
+            Repr {
+                x: self.inner.point_poss(Axis::X).collect(),
+                y: self.inner.point_poss(Axis::Y).collect(),
+                z: self.inner.point_poss(Axis::Z).collect(),
+                voxels: packed,
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Array {
+        /// Deserializes the shape from the format of [`VoxelShapeSlice`]'s serialization.
+        ///
+        /// The resolution of each axis is not limited, as shapes merged from
+        /// different resolutions may exceed [`MAX_SHAPE_RESOLUTION`](crate::MAX_SHAPE_RESOLUTION).
+        /// The packed voxels must fit the resolution exactly, so the size of the
+        /// shape is bounded by the size of the input.
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            use serde::de::Error;
+
+            #[derive(Deserialize)]
+            struct Repr {
+                x: Box<[f64]>,
+                y: Box<[f64]>,
+                z: Box<[f64]>,
+                voxels: Vec<u64>,
+            }
+
+            let Repr { x, y, z, voxels } = Repr::deserialize(deserializer)?;
+            let mut lens = [0u32; 3];
+            for (len, (axis, points)) in lens.iter_mut().zip([("x", &x), ("y", &y), ("z", &z)]) {
+                if !points.windows(2).all(|w| w[0] <= w[1]) || points.iter().any(|p| !p.is_finite())
+                {
+                    return Err(D::Error::custom(format!(
+                        "point positions of axis {axis} are not ascending finite numbers"
+                    )));
+                }
+                *len = points.len().saturating_sub(1).try_into().map_err(|_| {
+                    D::Error::custom(format!("resolution of axis {axis} is too large"))
+                })?;
+            }
+
+            let [len_x, len_y, len_z] = lens;
+            let total = (len_x as usize)
+                .checked_mul(len_y as usize)
+                .and_then(|xy| xy.checked_mul(len_z as usize));
+            if total.is_none_or(|total| voxels.len() != total.div_ceil(64)) {
+                return Err(D::Error::invalid_length(
+                    voxels.len(),
+                    &"packed voxels fitting the resolution",
+                ));
+            }
+
+            let mut set = VoxelSet::new(Props {
+                len_x,
+                len_y,
+                len_z,
+            });
+            let mut i = 0usize;
+            for x in 0..len_x {
+                for y in 0..len_y {
+                    for z in 0..len_z {
+                        if voxels[i / 64] & (1 << (i % 64)) != 0 {
+                            set.set(x, y, z);
+                        }
+                        i += 1;
+                    }
+                }
+            }
+
+            Ok(Array {
+                raw: RawVoxelShape {
+                    voxels: set,
+                    shape_cache: Vec::new(),
+                    bbox_cache: OnceLock::new(),
+                },
+                xp: x,
+                yp: y,
+                zp: z,
+            })
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! Voxel sets.
 
 use std::{
+    convert::Infallible,
     fmt::Debug,
     ops::{Deref, DerefMut, RangeInclusive},
 };
@@ -71,6 +72,85 @@ impl<'s> VoxelSetSlice<'s> {
         self.inner.bounds(axis)
     }
 
+    /// Iterates over boxes covering all voxels in this set, passing the minimum
+    /// and the exclusive maximum corners of each box.
+    ///
+    /// Adjacent voxels are greedily merged along X, then Y, then Z,
+    /// producing maximal boxes.
+    pub fn for_each_box<F>(&self, mut f: F)
+    where
+        F: FnMut([u32; 3], [u32; 3]),
+    {
+        let Ok(()) = self.try_for_each_box(|min, max| {
+            f(min, max);
+            Ok::<_, Infallible>(())
+        });
+    }
+
+    /// Iterates over boxes like [`Self::for_each_box`], stopping at the first
+    /// error returned by the visitor.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error returned by the visitor.
+    pub fn try_for_each_box<E, F>(&self, mut f: F) -> Result<(), E>
+    where
+        F: FnMut([u32; 3], [u32; 3]) -> Result<(), E>,
+    {
+        let (len_x, len_y, len_z) = (
+            self.len_of(Axis::X),
+            self.len_of(Axis::Y),
+            self.len_of(Axis::Z),
+        );
+        let index = |x: u32, y: u32, z: u32| ((x * len_y + y) * len_z + z) as usize;
+        let mut used = bitbox![0; (len_x as usize) * (len_y as usize) * (len_z as usize)];
+        let avail = |used: &BitSlice, x: u32, y: u32, z: u32| {
+            self.contains(x, y, z) && !used[index(x, y, z)]
+        };
+
+        for z in 0..len_z {
+            for y in 0..len_y {
+                for x in 0..len_x {
+                    if !avail(&used, x, y, z) {
+                        continue;
+                    }
+
+                    let mut x2 = x + 1;
+                    while x2 < len_x && avail(&used, x2, y, z) {
+                        x2 += 1;
+                    }
+                    let mut y2 = y + 1;
+                    while y2 < len_y && (x..x2).all(|i| avail(&used, i, y2, z)) {
+                        y2 += 1;
+                    }
+                    let mut z2 = z + 1;
+                    while z2 < len_z && (x..x2).all(|i| (y..y2).all(|j| avail(&used, i, j, z2))) {
+                        z2 += 1;
+                    }
+
+                    for i in x..x2 {
+                        for j in y..y2 {
+                            for k in z..z2 {
+                                used.set(index(i, j, k), true);
+                            }
+                        }
+                    }
+                    f([x, y, z], [x2, y2, z2])?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether this set contains no voxels.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        [Axis::X, Axis::Y, Axis::Z].into_iter().any(|axis| {
+            let bounds = self.bounds_of(axis);
+            bounds.start() >= bounds.end()
+        })
+    }
+
     /// Crops this set into a cropped slice.
     pub fn crop<'a>(&'a self, bounds: Bounds) -> Cropped<'a, 's> {
         Cropped {
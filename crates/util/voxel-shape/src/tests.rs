@@ -0,0 +1,495 @@
+use std::sync::{Arc, OnceLock};
+
+use rimecraft_voxel_math::{
+    direction::{Axis, Direction},
+    BBox, DVec3,
+};
+
+use crate::{
+    set::{Props, VoxelSet},
+    RawVoxelShape, Simple, VoxelShapeSlice,
+};
+
+fn simple(len: (u32, u32, u32), cells: &[(u32, u32, u32)]) -> Simple {
+    let mut voxels = VoxelSet::new(Props {
+        len_x: len.0,
+        len_y: len.1,
+        len_z: len.2,
+    });
+    for &(x, y, z) in cells {
+        voxels.set(x, y, z);
+    }
+    Simple(RawVoxelShape {
+        voxels,
+        shape_cache: Vec::new(),
+        bbox_cache: OnceLock::new(),
+    })
+}
+
+#[test]
+fn covers_face() {
+    let cube = simple((1, 1, 1), &[(0, 0, 0)]);
+    assert!(Direction::ALL.into_iter().all(|dir| cube.covers_face(dir)));
+
+    let empty = simple((1, 1, 1), &[]);
+    assert!(!Direction::ALL.into_iter().any(|dir| empty.covers_face(dir)));
+
+    let slab = simple((1, 2, 1), &[(0, 0, 0)]);
+    assert!(slab.covers_face(Direction::Down));
+    assert!(!slab.covers_face(Direction::Up));
+    assert!(!slab.covers_face(Direction::North));
+}
+
+#[test]
+fn offset() {
+    let cube = simple((1, 1, 1), &[(0, 0, 0)]);
+
+    let borrowed = cube.offset_borrowed(DVec3::new(0.5, 0.0, 0.0));
+    assert_eq!(
+        borrowed.inner.point_poss(Axis::X).collect::<Vec<_>>(),
+        [0.5, 1.5]
+    );
+    assert!(!borrowed.covers_face(Direction::West));
+    assert!(!borrowed.covers_face(Direction::Up));
+    assert!(cube.offset_borrowed(DVec3::ZERO).covers_face(Direction::Up));
+
+    let owned = cube.offset(DVec3::new(0.0, 0.0, -1.0));
+    assert_eq!(
+        owned.inner.point_poss(Axis::Z).collect::<Vec<_>>(),
+        [-1.0, 0.0]
+    );
+    assert_eq!(
+        owned.inner.point_poss(Axis::X).collect::<Vec<_>>(),
+        [0.0, 1.0]
+    );
+}
+
+#[test]
+fn slice_facing() {
+    let slab = simple((1, 2, 1), &[(0, 0, 0)]);
+
+    let bottom = slab.slice_facing(Direction::Down);
+    assert_eq!(bottom.inner.as_raw().voxels.len_of(Axis::Y), 1);
+    assert!(bottom.covers_face(Direction::Down));
+    assert!(bottom.covers_face(Direction::Up));
+
+    let top = slab.slice_facing(Direction::Up);
+    assert!(!Direction::ALL.into_iter().any(|dir| top.covers_face(dir)));
+
+    let side = slab.slice_facing(Direction::East);
+    assert_eq!(side.inner.as_raw().voxels.len_of(Axis::Y), 2);
+    assert!(side.inner.as_raw().voxels.contains(0, 0, 0));
+    assert!(!side.inner.as_raw().voxels.contains(0, 1, 0));
+}
+
+#[test]
+fn optimized_boxes() {
+    let mut cells = Vec::new();
+    for x in 0..4 {
+        for y in 0..4 {
+            for z in 0..4 {
+                cells.push((x, y, z));
+            }
+        }
+    }
+    let full = simple((4, 4, 4), &cells);
+    assert_eq!(full.optimized_boxes(), [BBox::new(DVec3::ZERO, DVec3::ONE)]);
+
+    let stairs = simple((2, 2, 1), &[(0, 0, 0), (1, 0, 0), (0, 1, 0)]);
+    assert_eq!(
+        stairs.optimized_boxes(),
+        [
+            BBox::new(DVec3::ZERO, DVec3::new(1.0, 0.5, 1.0)),
+            BBox::new(DVec3::new(0.0, 0.5, 0.0), DVec3::new(0.5, 1.0, 1.0)),
+        ]
+    );
+
+    assert!(simple((2, 2, 2), &[]).optimized_boxes().is_empty());
+}
+
+#[test]
+fn union_all() {
+    use crate::func;
+
+    assert!(func::union_all([]).is_empty());
+    assert!(func::union_all([func::empty(), func::empty()]).is_empty());
+
+    let bottom = Arc::from(simple((1, 2, 1), &[(0, 0, 0)]).into_boxed_slice());
+    let west = Arc::from(simple((2, 1, 1), &[(0, 0, 0)]).into_boxed_slice());
+    let union = func::union_all([bottom, func::empty(), west]);
+    assert!(union.covers_face(Direction::Down));
+    assert!(union.covers_face(Direction::West));
+    assert!(!union.covers_face(Direction::Up));
+    assert!(!union.covers_face(Direction::East));
+    assert_eq!(
+        union.optimized_boxes(),
+        [
+            BBox::new(DVec3::ZERO, DVec3::new(1.0, 0.5, 1.0)),
+            BBox::new(DVec3::new(0.0, 0.5, 0.0), DVec3::new(0.5, 1.0, 1.0)),
+        ]
+    );
+
+    let cube = func::union_all([func::full_cube(), func::full_cube()]);
+    assert!(Direction::ALL.into_iter().all(|dir| cube.covers_face(dir)));
+}
+
+#[test]
+fn rasterize() {
+    use crate::func;
+
+    let shape = func::rasterize(
+        BBox::new(DVec3::new(0.1, 0.0, 0.0), DVec3::new(0.5, 0.3, 1.5)),
+        4,
+    );
+    assert_eq!(
+        shape.optimized_boxes(),
+        [BBox::new(DVec3::ZERO, DVec3::new(0.5, 0.5, 1.0))]
+    );
+
+    assert!(func::rasterize(
+        BBox::new(DVec3::new(1.0, 0.0, 0.0), DVec3::new(2.0, 1.0, 1.0)),
+        4
+    )
+    .is_empty());
+}
+
+#[test]
+fn extents() {
+    let shape = simple((4, 2, 1), &[(1, 0, 0), (2, 0, 0)]).into_boxed_slice();
+    assert_eq!(
+        shape.extents(),
+        Some((DVec3::new(0.25, 0.0, 0.0), DVec3::new(0.75, 0.5, 1.0)))
+    );
+    assert_eq!(shape.min(Axis::X), 0.25);
+    assert_eq!(shape.max(Axis::Y), 0.5);
+
+    let empty = crate::func::empty();
+    assert!(empty.extents().is_none());
+    assert_eq!(empty.min(Axis::X), f64::INFINITY);
+    assert_eq!(empty.max(Axis::X), f64::NEG_INFINITY);
+}
+
+#[test]
+fn bounding_box() {
+    let shape = simple((2, 2, 2), &[(1, 0, 1)]).into_boxed_slice();
+    let bbox = BBox::new(DVec3::new(0.5, 0.0, 0.5), DVec3::new(1.0, 0.5, 1.0));
+    assert_eq!(shape.bounding_box(), Some(bbox));
+    assert_eq!(shape.bounding_box(), Some(bbox));
+
+    let offset = shape.offset_borrowed(DVec3::new(1.0, 0.0, 0.0));
+    assert_eq!(
+        offset.bounding_box(),
+        Some(BBox::new(
+            DVec3::new(1.5, 0.0, 0.5),
+            DVec3::new(2.0, 0.5, 1.0)
+        ))
+    );
+
+    assert_eq!(
+        crate::func::full_cube().bounding_box(),
+        Some(BBox::new(DVec3::ZERO, DVec3::ONE))
+    );
+    assert!(crate::func::empty().bounding_box().is_none());
+}
+
+#[test]
+fn try_for_each_box() {
+    let shape = simple((2, 2, 1), &[(0, 0, 0), (1, 0, 0), (0, 1, 0)]).into_boxed_slice();
+
+    let mut visited = 0;
+    let found = shape.try_for_each_box(|bbox| {
+        visited += 1;
+        if bbox.min().y >= 0.5 {
+            Err(bbox)
+        } else {
+            Ok(())
+        }
+    });
+    assert_eq!(
+        found,
+        Err(BBox::new(
+            DVec3::new(0.0, 0.5, 0.0),
+            DVec3::new(0.5, 1.0, 1.0)
+        ))
+    );
+    assert_eq!(visited, 2);
+
+    assert_eq!(shape.try_for_each_box(|_| Ok::<_, ()>(())), Ok(()));
+}
+
+#[test]
+fn combine() {
+    use crate::func::{self, BooleanOp};
+
+    let cube = func::full_cube();
+    let shifted = cube.offset(DVec3::new(0.5, 0.0, 0.0));
+    let bbox = |min: f64, max: f64| BBox::new(DVec3::new(min, 0.0, 0.0), DVec3::new(max, 1.0, 1.0));
+
+    let union = func::union(&cube, &shifted);
+    assert_eq!(union.bounding_box(), Some(bbox(0.0, 1.5)));
+    assert_eq!(union.optimized_boxes(), [bbox(0.0, 1.5)]);
+    assert_eq!(
+        func::combine(&cube, &shifted, BooleanOp::And).optimized_boxes(),
+        [bbox(0.5, 1.0)]
+    );
+    assert_eq!(
+        func::combine(&cube, &shifted, BooleanOp::OnlyFirst).optimized_boxes(),
+        [bbox(0.0, 0.5)]
+    );
+    assert_eq!(
+        func::combine(&cube, &shifted, BooleanOp::NotSame).optimized_boxes(),
+        [bbox(0.0, 0.5), bbox(1.0, 1.5)]
+    );
+    assert!(func::combine(&cube, &cube, BooleanOp::OnlySecond).is_empty());
+    assert!(func::union(&func::empty(), &func::empty()).is_empty());
+
+    // Halves and thirds interleave into sixths.
+    let half = Arc::from(simple((2, 1, 1), &[(0, 0, 0)]).into_boxed_slice());
+    let third = Arc::from(simple((3, 1, 1), &[(0, 0, 0)]).into_boxed_slice());
+    assert_eq!(
+        func::combine(&half, &third, BooleanOp::OnlyFirst).optimized_boxes(),
+        [bbox(1.0 / 3.0, 0.5)]
+    );
+}
+
+#[test]
+#[should_panic]
+fn combine_infinite() {
+    use crate::func::{self, BooleanOp};
+
+    func::combine(&func::empty(), &func::empty(), BooleanOp::NotOr);
+}
+
+#[test]
+fn raycast() {
+    use rimecraft_voxel_math::BlockPos;
+
+    let cube = crate::func::full_cube();
+    let pos = BlockPos::new(1, 0, 0);
+
+    let hit = cube
+        .raycast(DVec3::new(0.0, 0.5, 0.5), DVec3::new(3.0, 0.5, 0.5), pos)
+        .unwrap();
+    assert_eq!(hit.pos, DVec3::new(1.0, 0.5, 0.5));
+    assert_eq!(hit.side, Direction::West);
+    assert_eq!(hit.block_pos, pos);
+    assert!(!hit.inside_block);
+
+    // Grazing the top edge.
+    let hit = cube
+        .raycast(DVec3::new(0.0, 1.0, 0.5), DVec3::new(3.0, 1.0, 0.5), pos)
+        .unwrap();
+    assert_eq!(hit.pos, DVec3::new(1.0, 1.0, 0.5));
+    assert_eq!(hit.side, Direction::West);
+
+    assert!(cube
+        .raycast(DVec3::new(0.0, 1.5, 0.5), DVec3::new(3.0, 1.5, 0.5), pos)
+        .is_none());
+    assert!(cube
+        .raycast(DVec3::new(0.0, 0.5, 0.5), DVec3::new(0.9, 0.5, 0.5), pos)
+        .is_none());
+
+    let hit = cube
+        .raycast(DVec3::new(1.5, 0.5, 0.5), DVec3::new(1.5, 2.5, 0.5), pos)
+        .unwrap();
+    assert!(hit.inside_block);
+    assert_eq!(hit.side, Direction::Down);
+
+    // The ray passes over the lower step and hits the upper one.
+    let stairs = simple((2, 2, 1), &[(0, 0, 0), (1, 0, 0), (1, 1, 0)]).into_boxed_slice();
+    let hit = stairs
+        .raycast(
+            DVec3::new(-1.0, 0.75, 0.5),
+            DVec3::new(2.0, 0.75, 0.5),
+            BlockPos::ORIGIN,
+        )
+        .unwrap();
+    assert_eq!(hit.pos, DVec3::new(0.5, 0.75, 0.5));
+    assert_eq!(hit.side, Direction::West);
+}
+
+#[test]
+fn boxes() {
+    let cube = crate::func::full_cube();
+    assert_eq!(
+        cube.boxes().collect::<Vec<_>>(),
+        [BBox::new(DVec3::ZERO, DVec3::ONE)]
+    );
+
+    let l = simple((2, 2, 1), &[(0, 0, 0), (1, 0, 0), (0, 1, 0)]).into_boxed_slice();
+    let mut count = 0;
+    l.for_each_box(|_| count += 1);
+    assert_eq!(count, 2);
+    assert_eq!(l.boxes().len(), 2);
+}
+
+#[test]
+fn calculate_max_distance() {
+    // A wall occupying the east half of the block.
+    let wall = simple((2, 1, 1), &[(1, 0, 0)]).into_boxed_slice();
+    let entity = BBox::new(DVec3::new(-0.5, 0.0, 0.0), DVec3::new(0.25, 0.5, 0.5));
+
+    assert_eq!(wall.calculate_max_distance(Axis::X, &entity, 1.0), 0.25);
+    assert_eq!(wall.calculate_max_distance(Axis::X, &entity, 0.1), 0.1);
+    assert_eq!(wall.calculate_max_distance(Axis::X, &entity, -1.0), -1.0);
+    assert_eq!(wall.calculate_max_distance(Axis::X, &entity, 1.0e-8), 0.0);
+
+    let east = BBox::new(DVec3::new(1.5, 0.0, 0.0), DVec3::new(2.0, 0.5, 0.5));
+    assert_eq!(wall.calculate_max_distance(Axis::X, &east, -2.0), -0.5);
+
+    let above = BBox::new(DVec3::new(-0.5, 1.0, 0.0), DVec3::new(0.25, 1.5, 0.5));
+    assert_eq!(wall.calculate_max_distance(Axis::X, &above, 1.0), 1.0);
+
+    assert_eq!(
+        crate::func::empty().calculate_max_distance(Axis::Y, &entity, -3.0),
+        -3.0
+    );
+}
+
+#[test]
+fn rotate() {
+    use rimecraft_voxel_math::direction::BlockRotation;
+
+    use crate::func;
+
+    let cube = func::full_cube();
+    assert!(Arc::ptr_eq(&cube.rotate(BlockRotation::Clockwise90), &cube));
+    let empty = func::empty();
+    assert!(Arc::ptr_eq(
+        &empty.rotate(BlockRotation::Clockwise180),
+        &empty
+    ));
+
+    // A slab on the north half, with a post in its north-west corner.
+    let shape: Arc<VoxelShapeSlice<'_>> =
+        Arc::from(simple((2, 2, 2), &[(0, 0, 0), (1, 0, 0), (0, 1, 0)]).into_boxed_slice());
+    let rotated = shape.rotate(BlockRotation::Clockwise90);
+    assert_eq!(
+        rotated.optimized_boxes(),
+        [
+            BBox::new(DVec3::new(0.5, 0.0, 0.0), DVec3::new(1.0, 1.0, 0.5)),
+            BBox::new(DVec3::new(0.5, 0.0, 0.5), DVec3::new(1.0, 0.5, 1.0)),
+        ]
+    );
+    assert_eq!(
+        shape
+            .rotate(BlockRotation::Counterclockwise90)
+            .optimized_boxes(),
+        shape
+            .rotate(BlockRotation::Clockwise180)
+            .rotate(BlockRotation::Clockwise90)
+            .optimized_boxes()
+    );
+
+    let full_turn = (0..4).fold(shape.clone(), |s, _| s.rotate(BlockRotation::Clockwise90));
+    assert_eq!(full_turn.optimized_boxes(), shape.optimized_boxes());
+}
+
+#[test]
+fn cuboid() {
+    use crate::func;
+
+    let slab = func::cuboid(BBox::new(DVec3::ZERO, DVec3::new(1.0, 0.5, 1.0)));
+    assert_eq!(slab.min(Axis::Y), 0.0);
+    assert_eq!(slab.max(Axis::Y), 0.5);
+    assert_eq!(slab.max(Axis::X), 1.0);
+
+    // Neither on a grid nor within the block.
+    let post = func::cuboid(BBox::new(
+        DVec3::new(0.3, 0.0, 0.3),
+        DVec3::new(0.7, 1.5, 0.7),
+    ));
+    assert_eq!(post.min(Axis::X), 0.3);
+    assert_eq!(post.max(Axis::X), 0.7);
+    assert_eq!(post.max(Axis::Y), 1.5);
+
+    assert!(Arc::ptr_eq(
+        &func::cuboid(BBox::new(DVec3::ZERO, DVec3::ONE)),
+        &func::full_cube()
+    ));
+    assert!(func::cuboid(BBox::new(DVec3::ZERO, DVec3::new(1.0, 0.0, 1.0))).is_empty());
+}
+
+#[test]
+fn from_boxes() {
+    use crate::func;
+
+    let boxes = [
+        BBox::new(DVec3::ZERO, DVec3::new(1.0, 0.5, 1.0)),
+        BBox::new(DVec3::new(0.0, 0.5, 0.0), DVec3::new(0.25, 1.0, 1.0)),
+    ];
+    assert_eq!(func::from_boxes(boxes).optimized_boxes(), boxes);
+
+    let shape = func::from_boxes([
+        BBox::new(DVec3::ZERO, DVec3::new(1.0, 0.5, 1.0)),
+        BBox::new(DVec3::new(0.3, 0.5, 0.3), DVec3::new(0.7, 1.2, 0.7)),
+    ]);
+    assert_eq!(
+        shape.bounding_box(),
+        Some(BBox::new(DVec3::ZERO, DVec3::new(1.0, 1.2, 1.0)))
+    );
+
+    assert!(func::from_boxes([]).is_empty());
+}
+
+#[test]
+fn is_full_cube() {
+    assert!(crate::func::full_cube().is_full_cube());
+    assert!(simple((1, 1, 1), &[(0, 0, 0)])
+        .into_boxed_slice()
+        .is_full_cube());
+    let slab = simple((2, 2, 2), &[(0, 0, 0), (1, 0, 0), (0, 0, 1), (1, 0, 1)]);
+    assert!(!slab.into_boxed_slice().is_full_cube());
+    assert!(!crate::func::empty().is_full_cube());
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde_round_trip() {
+    use crate::{
+        func::{self, BooleanOp},
+        Array, MAX_SHAPE_RESOLUTION,
+    };
+
+    let stairs = func::from_boxes([
+        BBox::new(DVec3::ZERO, DVec3::new(1.0, 0.5, 1.0)),
+        BBox::new(DVec3::new(0.0, 0.5, 0.0), DVec3::new(0.5, 1.0, 1.0)),
+    ]);
+    let post = func::cuboid(BBox::new(
+        DVec3::new(0.3, 0.0, 0.3),
+        DVec3::new(0.7, 1.2, 0.7),
+    ));
+    let combined = func::combine(&stairs, &post, BooleanOp::Or);
+    let fence = func::union_all((0..6).map(|i| {
+        let min = f64::from(i) * 0.125;
+        func::cuboid(BBox::new(
+            DVec3::new(min, 0.0, 0.0),
+            DVec3::new(min + 0.0625, 1.0, 1.0),
+        ))
+    }));
+    assert!(fence.inner.point_poss(Axis::X).count() > MAX_SHAPE_RESOLUTION as usize + 1);
+
+    for shape in [
+        func::full_cube(),
+        simple((2, 2, 2), &[(0, 0, 0), (1, 0, 0), (0, 0, 1), (1, 0, 1)])
+            .into_boxed_slice()
+            .into(),
+        post,
+        combined,
+        fence,
+    ] {
+        let json = serde_json::to_string(&*shape).unwrap();
+        let decoded: Array = serde_json::from_str(&json).unwrap();
+        for axis in [Axis::X, Axis::Y, Axis::Z] {
+            assert!(decoded
+                .inner
+                .point_poss(axis)
+                .eq(shape.inner.point_poss(axis)));
+        }
+        assert_eq!(
+            decoded.boxes().collect::<Vec<_>>(),
+            shape.boxes().collect::<Vec<_>>()
+        );
+        assert_eq!(serde_json::to_string(&*decoded).unwrap(), json);
+    }
+}